@@ -0,0 +1,124 @@
+use crate::{Appr, Zero, One};
+use crate::polynomial::Polynomial;
+use num_complex::Complex;
+
+// |z|, from |z|^2 rounded to about twice the digits
+fn modulus<R: Appr>(z: &Complex<R>, accu: i64) -> R {
+	z.norm_sqr().accu(2 * accu + 2).sqrt()
+}
+
+// the smallest power of 2 not below x^(1/k), x > 0
+fn root_bound<R: Appr>(x: &R, k: usize) -> R {
+	let pow = |r: &R| (0..k).fold(R::one(), |a, _| a * r.clone());
+	let two = R::from(2);
+	let mut r = R::one();
+	if *x >= R::one() {
+		while pow(&r) < *x {
+			r = r * two.clone();
+		}
+	} else {
+		while pow(&(r.clone() / two.clone())) >= *x {
+			r = r / two.clone();
+		}
+	}
+	r
+}
+
+impl<T: Clone> Polynomial<T> {
+// all complex roots by the Aberth–Ehrlich iteration, started on a circle enclosing them (Fujiwara's bound),
+// for real or complex coefficients; with each root comes the radius n|p(z)/p'(z)| of a disk around it that contains
+// a root. The sweeps stop when no correction exceeds about eps(accu) relative to the root, or after max_iter of them
+	pub fn complex_roots<R>(&self, accu: i64, max_iter: usize) -> Vec<(Complex<R>, R)> where R: Appr, T: Into<Complex<R>> {
+		let a = self.as_ref().iter().cloned().map(Into::into).collect::<Vec<Complex<R>>>();
+// a polynomial without coefficients, as from an empty vector, has no roots to find, nor have the constants
+		if a.len() < 2 {
+			return vec![]
+		}
+		let n = a.len() - 1;
+		let round = |z: Complex<R>| Complex::new(z.re.accu(accu), z.im.accu(accu));
+		let lead = modulus(&a[n], accu);
+		let bound = (1..=n)
+			.map(|k| (k, modulus(&a[n-k], accu) / lead.clone()))
+			.filter(|(_, x)| *x > R::zero())
+			.map(|(k, x)| root_bound(&x, k))
+			.fold(R::zero(), |m, r| if r > m {r} else {m}) * R::from(2);
+// z_k = bound·exp(iπ(4k + 1)/2n), the cosines being doubled
+		let mut z = (0..n as i64).map(|k| {
+			let n = n as i64;
+			let e = Complex::new(R::cos_rational_x2(4*k + 1, 2*n, accu), R::cos_rational_x2(n - 4*k - 1, 2*n, accu));
+			round(e * Complex::from(bound.clone() / R::from(2)))
+		}).collect::<Vec<_>>();
+		let eval = |x: &Complex<R>| {
+			let (mut p, mut d) = (Complex::<R>::zero(), Complex::<R>::zero());
+			for c in a.iter().rev() {
+				d = d * x.clone() + p.clone();
+				p = p * x.clone() + c.clone();
+			}
+			(p, d)
+		};
+		let eps2 = R::eps(accu) * R::eps(accu);
+		let one = Complex::<R>::one();
+		for _ in 0..max_iter {
+			let mut converged = true;
+			for i in 0..n {
+				let (p, d) = eval(&z[i]);
+				if p.is_zero() {
+					continue
+				}
+				if d.is_zero() {
+					converged = false;
+					continue
+				}
+				let newton = p / d;
+				let s = (0..n).filter(|&j| j != i).fold(Complex::<R>::zero(), |s, j| s + one.clone() / (z[i].clone() - z[j].clone()));
+				let w = newton.clone() / (one.clone() - newton * s);
+				if w.norm_sqr() > eps2.clone() * (R::one() + z[i].norm_sqr()) {
+					converged = false;
+				}
+				z[i] = round(z[i].clone() - w);
+			}
+			if converged {
+				break
+			}
+		}
+// at a critical point, the disk of radius 2·bound around z, |z| <= bound, contains all roots
+		let mut roots = z.into_iter().map(|x| {
+			let (p, d) = eval(&x);
+			let err = if p.is_zero() {
+				R::zero()
+			} else if d.is_zero() {
+				bound.clone() * R::from(2)
+			} else {
+				modulus(&(p / d), accu) * R::from(n as i32)
+			};
+			(x, err)
+		}).collect::<Vec<_>>();
+		roots.sort_by(|a, b| (a.0.re.clone(), a.0.im.clone()).partial_cmp(&(b.0.re.clone(), b.0.im.clone())).unwrap_or(std::cmp::Ordering::Equal));
+		roots
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bigdecimal::BigDecimal;
+
+	#[test]
+	fn aberth() {
+		let close = |z: &Complex<f64>, re: f64, im: f64| (z.re - re).abs() < 1e-12 && (z.im - im).abs() < 1e-12;
+		assert!(Polynomial::from(Vec::<f64>::new()).complex_roots::<f64>(0, 100).is_empty());
+		assert!(Polynomial::from(vec![3.0]).complex_roots::<f64>(0, 100).is_empty());
+		let r = Polynomial::from(vec![1.0, 0.0, 1.0]).complex_roots::<f64>(0, 100);
+		assert!(r.len() == 2 && close(&r[0].0, 0.0, -1.0) && close(&r[1].0, 0.0, 1.0));
+		let r = Polynomial::from(vec![-6.0, 11.0, -6.0, 1.0]).complex_roots::<f64>(0, 100);
+		assert!(r.iter().zip(&[1.0, 2.0, 3.0]).all(|((z, err), x)| close(z, *x, 0.0) && *err < 1e-12));
+		// complex coefficients: (x - i)(x - 2)
+		let p = Polynomial::from(vec![Complex::new(0.0, 2.0), Complex::new(-2.0, -1.0), Complex::new(1.0, 0.0)]);
+		let r = p.complex_roots::<f64>(0, 100);
+		assert!(close(&r[0].0, 0.0, 1.0) && close(&r[1].0, 2.0, 0.0));
+		let r = Polynomial::from(vec![BigDecimal::from(-2), BigDecimal::zero(), BigDecimal::one()]).complex_roots::<BigDecimal>(40, 100);
+		let sqrt2: BigDecimal = "1.4142135623730950488016887242096980785696718753769".parse().unwrap();
+		assert!((r[1].0.re.clone() - sqrt2).abs() < BigDecimal::eps(38) && r[1].0.im.abs() < BigDecimal::eps(38));
+		assert!(r[1].1 < BigDecimal::eps(38));
+	}
+}
@@ -0,0 +1,98 @@
+use crate::{Zero, One, Appr};
+use std::ops;
+
+// midpoint-radius enclosure of a number: the exact value lies within rad of mid;
+// every operation adds the bound on its own rounding error (Appr::rounding_error) to the radius
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ball<T> {
+	pub mid: T,
+	pub rad: T,
+}
+
+impl<T: Appr> Ball<T> {
+	pub fn new(mid: T, rad: T) -> Self {
+		Ball {
+			mid,
+			rad,
+		}
+	}
+
+	pub fn exact(mid: T) -> Self {
+		Ball::new(mid, T::zero())
+	}
+
+	fn rounded(mid: T, rad: T) -> Self {
+		let e = T::rounding_error(&mid);
+		Ball::new(mid, rad + e)
+	}
+
+// the midpoint rounded to accu digits, what is cut off is added to the radius
+	pub fn accu(self, accu: i64) -> Self {
+		let mid = self.mid.clone().accu(accu);
+		let rad = self.rad + (self.mid - mid.clone()).abs();
+		Ball::new(mid, rad)
+	}
+}
+
+impl<T: Appr> ops::Add for Ball<T> {
+	type Output = Self;
+	fn add(self, rhs: Self) -> Self {
+		Ball::rounded(self.mid + rhs.mid, self.rad + rhs.rad)
+	}
+}
+
+impl<T: Appr> ops::Sub for Ball<T> {
+	type Output = Self;
+	fn sub(self, rhs: Self) -> Self {
+		Ball::rounded(self.mid - rhs.mid, self.rad + rhs.rad)
+	}
+}
+
+impl<T: Appr> ops::Neg for Ball<T> {
+	type Output = Self;
+	fn neg(self) -> Self {
+		Ball::new(-self.mid, self.rad)
+	}
+}
+
+impl<T: Appr> ops::Mul for Ball<T> {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self {
+		let rad = self.mid.abs() * rhs.rad.clone() + rhs.mid.abs() * self.rad.clone() + self.rad * rhs.rad;
+		Ball::rounded(self.mid * rhs.mid, rad)
+	}
+}
+
+impl<T: Appr> Zero for Ball<T> {
+	fn zero() -> Self {
+		Ball::exact(T::zero())
+	}
+
+	fn is_zero(&self) -> bool {
+		self.mid.is_zero() && self.rad.is_zero()
+	}
+}
+
+impl<T: Appr> One for Ball<T> {
+	fn one() -> Self {
+		Ball::exact(T::one())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn propagation() {
+		let a = Ball::new(2.0, 0.1);
+		let b = Ball::new(-3.0, 0.01);
+		let p = a.clone() * b.clone();
+		assert_eq!(p.mid, -6.0);
+		assert!(p.rad >= 0.32 && p.rad < 0.33);
+		let s = a - b;
+		assert!(s.mid == 5.0 && s.rad >= 0.11 && s.rad < 0.11 + 1e-14);
+		let r = Ball::exact(bigdecimal::BigDecimal::from(1) / bigdecimal::BigDecimal::from(16)).accu(2);
+		assert_eq!(r, Ball::new("0.06".parse().unwrap(), "0.0025".parse().unwrap()));
+	}
+}
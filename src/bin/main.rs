@@ -1,18 +1,65 @@
-use hofstadter_butterfly::Appr;
+use hofstadter_butterfly::{Appr, Hopping, Lattice, Isolation, Interval, Cyclotomic, Complex, ResultCache};
 
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
 
-const HELP: &'static str = "\
+use std::{str::FromStr, fmt::{Debug, Display}, collections::BTreeMap, sync::{mpsc, atomic::{AtomicUsize, Ordering}}, thread, fs, io::Write};
+
+const HELP: &str = "\
 Usage:
 <run> <t> trq <p> <q>
 <run> <t> trq_upto <qmax>
 <run> <t> intervals <p> <q>
-<run> <t> check
-<run> <t> check_full
+<run> <t> bloch <p> <q> <kx> <ky>  (square lattice, momenta in units of π, e.g. 1/4)
+<run> <t> roots <p> <q>       (square lattice: all complex roots of P(E) = ±c, whose real ones are the band edges)
+<run> e discriminant <p> <q> (square lattice: discriminants of P(E) ∓ c, zero where two band edges collide)
+<run> <t> check               (square lattice)
+<run> <t> check_full          (square lattice)
 <run> <t> intervals_upto <qmax>
 <run> <t> intervals_farey <number of Farey iterations>
+<run> <t> merge <sweep> <qmax or iterations> <file>...  (the lines of trq_upto, intervals_upto or intervals_farey
+                              from the files of its shards, in the order of the sweep, each flux once)
+
+Underlying type <t> is 'f' for f64, 'd' for BigDecimal or 'i' for f64 intervals: with 'i', every band edge is
+printed as an interval certified to contain it (square lattice without --diagonal and --second, polynomial backend),
+or the energies where the signs could not be decided are reported (q above about 20).
+With 'e' (exact), trq prints the coefficients exactly: rational, or polynomials in c = 2cos(π/2q),
+and intervals, check and check_full count the bands exactly by Sturm sequences, bands that touch counting once,
+while discriminant tells exactly whether a gap is closed
+(square lattice without --diagonal and --second, rational λ).
+
+Options (may be given anywhere after <t>):
+--lattice <l>   'square' (default), 'honeycomb' or 'triangular'
+--lambda <λ>    on-site amplitude of the Aubry–André chain, λ = 2t_y/t_x (default 2, the square lattice);
+                on the honeycomb lattice, twice the hopping between zigzag chains
+                (the flux p/q is per hexagon there, and per two triangles on the triangular lattice,
+                whose spectrum has period 2 in the flux, so the sweeps cover p/q up to 1)
+--diagonal <t'> hopping along both diagonals of the square lattice (default 0)
+--second <t2>   hopping to second neighbours along x on the square lattice (default 0)
+--backend <b>   band edges from the characteristic 'polynomial' (default) or by bisection on the Harper 'matrix'
+                (square lattice without --diagonal and --second; accurate for large q even with f64)
+--isolation <m> real roots of the polynomials isolated by 'sturm' sequences (default) or by 'descartes' rule of
+                signs on halved intervals, with 'f' and 'd' and the polynomial backend
+--gaps          append the TKNN labels σ:s of the gaps between the intervals after ' | '
+--wannier       print the integrated density of states r/q in every open gap instead of the intervals
+                (the Wannier diagram)
+--threads <n>   fluxes computed at the same time by intervals_upto and intervals_farey, whose lines are still
+                printed in order (default: the number of CPUs); every flux starts the search for the accuracy
+                needed with 'd' at 3q/4 + 2 digits, so the output does not depend on n
+--output <file> write the lines of trq_upto, intervals_upto and intervals_farey to file as they are done, and
+                the sweep with its type and options and the number of fluxes done to file.checkpoint
+--resume        with --output: keep the complete lines of file, after checking that they begin the sweep, that
+                file.checkpoint is for the same sweep, type, options and shard, and that it counts them,
+                and append the rest of it
+--shard <i/n>   run only the i-th of n shards, 1 <= i <= n, of trq_upto, intervals_upto and intervals_farey; the
+                fluxes are assigned to shards by a hash of p/q, the same for every run, and merge joins the outputs
+                (given the same --lattice)
+--cache <dir>   keep the results of trq, trq_upto, intervals, intervals_upto and intervals_farey in dir, per flux,
+                type, options, accuracy and version of the crate and of its algorithms, and reuse them instead of
+                computing them again";
 
-Underlying type <t> is 'f' for f64 or 'd' for BigDecimal.";
+// the version of the computations and of their output in the cache keys, to be raised whenever a result changes
+// without a new crate version
+const ALGORITHM: u32 = 2;
 
 fn help() {
 	eprintln!("{}", HELP)
@@ -22,94 +69,378 @@ fn help() {
 enum UnderlyingType {
 	F64,
 	BigDecimal,
+	Interval,
+	Exact,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Options {
+	lattice: Option<String>,
+	lambda: Option<String>,
+	diagonal: Option<String>,
+	second: Option<String>,
+	backend: Option<String>,
+	isolation: Option<String>,
+	gaps: bool,
+	wannier: bool,
+	threads: Option<String>,
+	cache: Option<ResultCache>,
+	output: Option<String>,
+	resume: bool,
+	shard: Option<String>,
+}
+
+impl Options {
+	fn parse(args: impl Iterator<Item = String>) -> (Self, std::vec::IntoIter<String>) {
+		let mut opts = Options::default();
+		let mut positional = Vec::new();
+		let mut args = args;
+		while let Some(arg) = args.next() {
+			match arg.as_ref() {
+				"--lattice" => opts.lattice = Some(args.next().expect(HELP)),
+				"--lambda" => opts.lambda = Some(args.next().expect(HELP)),
+				"--diagonal" => opts.diagonal = Some(args.next().expect(HELP)),
+				"--second" => opts.second = Some(args.next().expect(HELP)),
+				"--backend" => opts.backend = Some(args.next().expect(HELP)),
+				"--isolation" => opts.isolation = Some(args.next().expect(HELP)),
+				"--gaps" => opts.gaps = true,
+				"--wannier" => opts.wannier = true,
+				"--threads" => opts.threads = Some(args.next().expect(HELP)),
+				"--cache" => opts.cache = Some(ResultCache::new(args.next().expect(HELP)).expect("cannot create the cache directory")),
+				"--output" => opts.output = Some(args.next().expect(HELP)),
+				"--resume" => opts.resume = true,
+				"--shard" => opts.shard = Some(args.next().expect(HELP)),
+				_ => positional.push(arg),
+			}
+		}
+		if opts.lattice() != Lattice::Square && (opts.diagonal.is_some() || opts.second.is_some()) {
+			panic!("--diagonal and --second are only supported on the square lattice");
+		}
+		if opts.resume && opts.output.is_none() {
+			panic!("--resume needs --output");
+		}
+		opts.shard();
+		opts.isolation();
+		if opts.matrix() && (opts.lattice() != Lattice::Square || opts.diagonal.is_some() || opts.second.is_some()) {
+			panic!("--backend matrix is only supported on the square lattice without --diagonal and --second");
+		}
+		(opts, positional.into_iter())
+	}
+	
+	fn lattice(&self) -> Lattice {
+		self.lattice.as_deref().unwrap_or("square").parse().expect(HELP)
+	}
+	
+	fn matrix(&self) -> bool {
+		match self.backend.as_deref().unwrap_or("polynomial") {
+			"polynomial" => false,
+			"matrix" => true,
+			_ => panic!("{}", HELP),
+		}
+	}
+	
+	fn isolation(&self) -> Isolation {
+		match self.isolation.as_deref().unwrap_or("sturm") {
+			"sturm" => Isolation::Sturm,
+			"descartes" => Isolation::Descartes,
+			_ => panic!("{}", HELP),
+		}
+	}
+	
+	fn shard(&self) -> Option<(usize, usize)> {
+		let (i, n) = self.shard.as_deref()?.split_once('/').expect(HELP);
+		let (i, n) = (i.parse().expect(HELP), n.parse().expect(HELP));
+		if i == 0 || i > n {
+			panic!("--shard i/n needs 1 <= i <= n");
+		}
+		Some((i, n))
+	}
+	
+	fn threads(&self) -> usize {
+		match &self.threads {
+			Some(n) => n.parse().ok().filter(|n| *n > 0).expect(HELP),
+			None => thread::available_parallelism().map_or(1, |n| n.get()),
+		}
+	}
+	
+// the options that change the results, for the keys of the cache and the checkpoints
+	fn results_key(&self) -> String {
+		format!(
+			"lattice={} lambda={} diagonal={} second={} backend={} isolation={:?} gaps={} wannier={}",
+			self.lattice.as_deref().unwrap_or("square"), self.lambda::<String>(),
+			self.diagonal.as_deref().unwrap_or("0"), self.second.as_deref().unwrap_or("0"),
+			self.backend.as_deref().unwrap_or("polynomial"), self.isolation(), self.gaps, self.wannier
+		)
+	}
+	
+// f() for the result of a command at p/q, through the cache if there is one; the key holds all options that change it
+	fn cached<F: FnOnce() -> String>(&self, command: &str, ut: UnderlyingType, p: usize, q: usize, f: F) -> String {
+		match &self.cache {
+			None => f(),
+			Some(cache) => {
+				let key = format!("{} {:?} {}/{} accu={} algorithm={} {}", command, ut, p, q, ut.accuracy(q), ALGORITHM, self.results_key());
+				cache.get_or_insert_with(&key, f)
+			},
+		}
+	}
+	
+// the sweep command with its argument, the type, the options and the shard, which the checkpoint records
+	fn sweep_key(&self, command: &str, n: usize, ut: UnderlyingType) -> String {
+		let (i, shards) = self.shard().unwrap_or((1, 1));
+		format!("{} {} {:?} algorithm={} {} shard={}/{}", command, n, ut, ALGORITHM, self.results_key(), i, shards)
+	}
+	
+	fn lambda<T: FromStr>(&self) -> T where T::Err: Debug {
+		self.lambda.as_deref().unwrap_or("2").parse().expect(HELP)
+	}
+	
+	fn hopping<T: FromStr>(&self) -> Hopping<T> where T::Err: Debug {
+		Hopping {
+			diagonal: self.diagonal.as_deref().unwrap_or("0").parse().expect(HELP),
+			second: self.second.as_deref().unwrap_or("0").parse().expect(HELP),
+		}
+	}
 }
 
 impl UnderlyingType {
-	fn trq(self, p: usize, q: usize) -> String {
+// the digits of 'd' at p/q, from which the sweeps search the accuracy needed; those of the other types are fixed
+	fn accuracy(self, q: usize) -> i64 {
+		if self == UnderlyingType::BigDecimal {q as i64 * 3 / 4 + 2} else {0}
+	}
+	
+	fn trq(self, p: usize, q: usize, opts: &Options) -> String {
+		match self {
+			UnderlyingType::F64 => trq::<f64>(p, q, 0, opts).iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::BigDecimal => trq::<BigDecimal>(p, q, self.accuracy(q), opts).iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::Interval => trq::<Interval>(p, q, 0, opts).iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::Exact => {
+				assert!(opts.lattice() == Lattice::Square && opts.hopping::<f64>().is_zero(), "{}", HELP);
+				Cyclotomic::trq(p, q, &Cyclotomic::from_decimal(&opts.lambda())).into_vec().iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", ")
+			},
+		}
+	}
+	
+	fn bloch(self, p: usize, q: usize, kx: (i64, i64), ky: (i64, i64), opts: &Options) -> String {
+		match self {
+			UnderlyingType::F64 => f64::bloch_energies(p, q, &opts.lambda(), kx, ky, 0).iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::BigDecimal => BigDecimal::bloch_energies(p, q, &opts.lambda(), kx, ky, self.accuracy(q)).iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::Interval | UnderlyingType::Exact => panic!("bloch is not supported with intervals or exact arithmetic"),
+		}
+	}
+	
+	fn roots(self, p: usize, q: usize, opts: &Options) -> String {
 		match self {
-			UnderlyingType::F64 => f64::trq(p, q, 0).as_ref().iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
-			UnderlyingType::BigDecimal => BigDecimal::trq(p, q, q as i64 * 3 / 4 + 2).as_ref().iter().map(|x| {format!("{}", x)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::F64 => complex_roots::<f64>(p, q, 0, opts),
+			UnderlyingType::BigDecimal => complex_roots::<BigDecimal>(p, q, self.accuracy(q), opts),
+			UnderlyingType::Interval | UnderlyingType::Exact => panic!("roots is not supported with intervals or exact arithmetic"),
 		}
 	}
 	
-	fn intervals(self, p: usize, q: usize) -> (usize, String) {
+	fn discriminant(self, p: usize, q: usize, opts: &Options) -> String {
+		assert!(opts.lattice() == Lattice::Square && opts.hopping::<f64>().is_zero(), "{}", HELP);
 		match self {
-			UnderlyingType::F64 => {
-				let pol = f64::intervals(p, q, 0);
-				(pol.len(), pol.iter().map(|x| {format!("{}..{}", x.0, x.1)}).collect::<Vec<_>>().join(", "))
+// rounded, a discriminant of degree 2q - 2 in the coefficients neither vanishes where it should nor stays finite
+			UnderlyingType::F64 | UnderlyingType::BigDecimal | UnderlyingType::Interval => panic!("discriminant is only supported with exact arithmetic"),
+			UnderlyingType::Exact => {
+				let (a, b) = Cyclotomic::edge_discriminants(p, q, &Cyclotomic::from_decimal(&opts.lambda()));
+				let closed = if a.is_zero() || b.is_zero() {"a gap is closed"} else {"all gaps are open"};
+				format!("P(E) - c: {}\nP(E) + c: {}\n{}", a, b, closed)
 			},
-			UnderlyingType::BigDecimal => {
-				let pol = BigDecimal::intervals(p, q, q as i64 * 3 / 4 + 2);
-				(pol.len(), pol.iter().map(|x| {format!("{}..{}", x.0, x.1)}).collect::<Vec<_>>().join(", "))
+		}
+	}
+	
+	fn intervals(self, p: usize, q: usize, opts: &Options) -> (usize, String) {
+		match self {
+			UnderlyingType::F64 => match intervals::<f64>(p, q, 0, opts) {
+				Ok(pol) => (pol.len(), format_intervals(p, q, &pol, opts)),
+				Err(s) => (0, s),
+			},
+			UnderlyingType::BigDecimal => match intervals::<BigDecimal>(p, q, self.accuracy(q), opts) {
+				Ok(pol) => (pol.len(), format_intervals(p, q, &pol, opts)),
+				Err(s) => (0, s),
+			},
+			UnderlyingType::Interval => match certified_intervals(p, q, opts) {
+				Ok(pol) => (pol.len(), format_intervals(p, q, &pol, opts)),
+				Err(s) => (0, s),
+			},
+			UnderlyingType::Exact => {
+				assert!(opts.lattice() == Lattice::Square && opts.hopping::<f64>().is_zero() && !opts.matrix(), "{}", HELP);
+				let n = Cyclotomic::band_count(p, q, &Cyclotomic::from_decimal(&opts.lambda()));
+				(n, format!("{} bands, counted exactly", n))
 			},
 		}
 	}
 	
-	fn intervals_auto(self, p: usize, q: usize, accu: &mut i64) -> String {
+// the accuracy is searched from 3q/4 + 2 digits up, for every flux on its own
+	fn intervals_auto(self, p: usize, q: usize, opts: &Options) -> String {
 		match self {
-			UnderlyingType::F64 => f64::
-				intervals(p, q, 0).iter().map(|x| {format!("{}..{}", x.0, x.1)}).collect::<Vec<_>>().join(", "),
+			UnderlyingType::F64 => intervals::<f64>(p, q, 0, opts).map_or_else(|s| s, |pol| format_intervals(p, q, &pol, opts)),
 			UnderlyingType::BigDecimal => {
-				let (pol, na) = BigDecimal::intervals_auto(p, q, *accu);
-				*accu = na.expect("Failed to find required accuracy");
-				pol.iter().map(|x| {format!("{}..{}", x.0, x.1)}).collect::<Vec<_>>().join(", ")
+				let start = self.accuracy(q).max(4);
+				let mut accu = start;
+				loop {
+					let (pol, na) = intervals_auto::<BigDecimal>(p, q, accu, opts);
+					if na.is_some() {
+						return format_intervals(p, q, &pol, opts)
+					}
+					accu += 20;
+					assert!(accu < start + 100, "Failed to find required accuracy");
+				}
 			},
+			UnderlyingType::Interval => certified_intervals(p, q, opts).map_or_else(|s| s, |pol| format_intervals(p, q, &pol, opts)),
+			UnderlyingType::Exact => panic!("only trq, intervals and check are supported with exact arithmetic"),
+		}
+	}
+}
+
+fn trq<T: Appr + FromStr>(p: usize, q: usize, accu: i64, opts: &Options) -> Vec<T> where T::Err: Debug {
+	let hop = opts.hopping();
+	if opts.lattice() == Lattice::Honeycomb {
+		T::trq_honeycomb(p, q, &opts.lambda(), accu).into_vec()
+	} else if opts.lattice() == Lattice::Triangular {
+		T::trq_triangular(p, q, &opts.lambda(), accu).into_vec()
+	} else if hop.is_zero() {
+		T::trq(p, q, &opts.lambda(), accu).into_vec()
+	} else {
+		T::trq_nnn(p, q, &opts.lambda(), &hop, (-1, 1), accu).trace().into_vec()
+	}
+}
+
+// the roots of P(E) - c and P(E) + c by the Aberth–Ehrlich iteration, each with the radius of a disk containing a root
+fn complex_roots<T: Appr + FromStr + Display>(p: usize, q: usize, accu: i64, opts: &Options) -> String where T::Err: Debug {
+	assert!(opts.lattice() == Lattice::Square && opts.hopping::<f64>().is_zero(), "{}", HELP);
+	let pol = T::trq(p, q, &opts.lambda(), accu);
+	let c = T::trace_bound(q, &opts.lambda(), accu);
+// roots within their error or the accuracy of the real axis are printed as real
+	let format = |r: Vec<(Complex<T>, T)>| r.into_iter().map(|(z, err)| {
+		let re = z.re.accu(accu);
+		if z.im.abs() <= err || z.im.abs() < T::eps(accu) {
+			format!("{} ±{}", re, err.accu(accu + 2))
+		} else {
+			format!("{} ±{}", Complex::new(re, z.im.accu(accu)), err.accu(accu + 2))
 		}
+	}).collect::<Vec<_>>().join(", ");
+	format!(
+		"P(E) = c: {}\nP(E) = -c: {}",
+		format((pol.clone() - c.clone()).complex_roots(accu, 500)),
+		format((pol + c).complex_roots(accu, 500))
+	)
+}
+
+fn format_intervals<T: Appr + Display>(p: usize, q: usize, int: &[(T, T)], opts: &Options) -> String {
+	let s = if !opts.wannier {
+		int.iter().map(|x| {format!("{}..{}", x.0, x.1)}).collect::<Vec<_>>().join(", ")
+	} else if let Some(counts) = T::gap_counts(q, opts.lattice(), int) {
+		counts.iter().zip(int.windows(2)).filter(|(_, w)| w[1].0 > w[0].1).map(|(r, _)| format!("{}/{}", r, q)).collect::<Vec<_>>().join(", ")
+	} else {
+		"?".to_string()
+	};
+	if !opts.gaps {
+		return s
+	}
+	match T::gap_labels(p, q, opts.lattice(), int) {
+		Some(labels) => format!("{} | {}", s, labels.iter().map(|(sigma, s)| format!("{}:{}", sigma, s)).collect::<Vec<_>>().join(", ")),
+		None => format!("{} | ?", s),
+	}
+}
+
+fn intervals<T: Appr + FromStr>(p: usize, q: usize, accu: i64, opts: &Options) -> Result<Vec<(T, T)>, String> where T::Err: Debug {
+	if opts.matrix() {
+		return Ok(T::intervals_matrix(p, q, &opts.lambda(), accu))
+	}
+	match opts.lattice() {
+		Lattice::Square => T::intervals_nnn_by(opts.isolation(), p, q, &opts.lambda(), &opts.hopping(), accu).ok_or_else(|| "no phase gave the full set of bands".to_string()),
+		lattice => Ok(T::intervals_by(opts.isolation(), p, q, lattice, &opts.lambda(), accu)),
+	}
+}
+
+fn intervals_auto<T: Appr + FromStr>(p: usize, q: usize, accu: i64, opts: &Options) -> (Vec<(T, T)>, Option<i64>) where T::Err: Debug {
+	if opts.matrix() {
+		return (T::intervals_matrix(p, q, &opts.lambda(), accu), Some(accu))
+	}
+	match opts.lattice() {
+		Lattice::Square => T::intervals_nnn_auto_by(opts.isolation(), p, q, &opts.lambda(), &opts.hopping(), accu),
+		lattice => T::intervals_auto_by(opts.isolation(), p, q, lattice, &opts.lambda(), accu),
 	}
 }
 
+fn certified_intervals(p: usize, q: usize, opts: &Options) -> Result<Vec<(Interval, Interval)>, String> {
+	assert!(opts.lattice() == Lattice::Square && opts.hopping::<f64>().is_zero() && !opts.matrix(), "{}", HELP);
+	Interval::certified_intervals(p, q, &opts.lambda()).map_err(|e| format!("undecidable in {}", e))
+}
+
 fn main() {
-	let mut args = std::env::args().skip(1);
-	let ut = match args.next().unwrap_or(String::new()).as_ref() {
+	let (opts, mut args) = Options::parse(std::env::args().skip(1));
+	let ut = match args.next().unwrap_or_default().as_ref() {
 		"f" => UnderlyingType::F64,
 		"d" => UnderlyingType::BigDecimal,
+		"i" => UnderlyingType::Interval,
+		"e" => UnderlyingType::Exact,
 		_ => {
 			help();
 			return
 		}
 	};
 	
-	match args.next().unwrap_or(String::new()).as_ref() {
+	let period = opts.lattice().flux_period();
+	match args.next().unwrap_or_default().as_ref() {
 		"trq" => {
 			let p: usize = args.next().expect(HELP).parse().expect(HELP);
 			let q: usize = args.next().expect(HELP).parse().expect(HELP);
-			println!("{}", ut.trq(p, q));
+			println!("{}", opts.cached("trq", ut, p, q, || ut.trq(p, q, &opts)));
 		},
 		"trq_upto" => {
-			let qmax: usize = args.next().expect(HELP).parse().expect(HELP);
-			for q in 1..=qmax {
-				for p in (1..=q/2).filter(|x| coprime(*x, q)) {
-					println!("[{}, {}] -> {},", p, q, ut.trq(p, q));
-				}
-			}
+			let n = args.next().expect(HELP).parse().expect(HELP);
+			let (fluxes, label) = sweep_fluxes("trq_upto", n, period);
+			sweep(&opts.sweep_key("trq_upto", n, ut), &fluxes, &opts, label, |p, q| format!("{},", opts.cached("trq", ut, p, q, || ut.trq(p, q, &opts))));
 		},
 		"intervals" => {
 			let p: usize = args.next().expect(HELP).parse().expect(HELP);
 			let q: usize = args.next().expect(HELP).parse().expect(HELP);
-			let (vl, vs) = ut.intervals(p, q);
-			println!("{} [{}]", vl, vs);
+			println!("{}", opts.cached("intervals", ut, p, q, || {
+				let (vl, vs) = ut.intervals(p, q, &opts);
+				format!("{} [{}]", vl, vs)
+			}));
 		},
-		"intervals_upto" => {
-			let qmax: usize = args.next().expect(HELP).parse().expect(HELP);
-			let mut accu = 4;
-			for q in 1..=qmax {
-				for p in (0..=q/2).filter(|x| coprime(*x, q)) {
-					let int = ut.intervals_auto(p, q, &mut accu);
-					println!("{}/{}: {}", p, q, int);
-				}
-			}
+		"roots" => {
+			let p: usize = args.next().expect(HELP).parse().expect(HELP);
+			let q: usize = args.next().expect(HELP).parse().expect(HELP);
+			println!("{}", ut.roots(p, q, &opts));
 		},
-		"intervals_farey" => {
-			let n: usize = args.next().expect(HELP).parse().expect(HELP);
-			let mut accu = 4;
-			for (p, q) in farey(n).into_iter() {
-				let int = ut.intervals_auto(p, q, &mut accu);
-				println!("{}/{}: {}", p, q, int);
+		"discriminant" => {
+			let p: usize = args.next().expect(HELP).parse().expect(HELP);
+			let q: usize = args.next().expect(HELP).parse().expect(HELP);
+			println!("{}", ut.discriminant(p, q, &opts));
+		},
+		"bloch" => {
+			assert!(opts.lattice() == Lattice::Square && opts.hopping::<f64>().is_zero(), "{}", HELP);
+			let p: usize = args.next().expect(HELP).parse().expect(HELP);
+			let q: usize = args.next().expect(HELP).parse().expect(HELP);
+			let kx = fraction(&args.next().expect(HELP));
+			let ky = fraction(&args.next().expect(HELP));
+			println!("{}", ut.bloch(p, q, kx, ky, &opts));
+		},
+		command @ "intervals_upto" | command @ "intervals_farey" => {
+			let n = args.next().expect(HELP).parse().expect(HELP);
+			let (fluxes, label) = sweep_fluxes(command, n, period);
+			sweep(&opts.sweep_key(command, n, ut), &fluxes, &opts, label, |p, q| opts.cached("intervals_auto", ut, p, q, || ut.intervals_auto(p, q, &opts)));
+		},
+		"merge" => {
+			let command = args.next().expect(HELP);
+			let (fluxes, label) = sweep_fluxes(&command, args.next().expect(HELP).parse().expect(HELP), period);
+			let files = args.map(|path| {
+				let content = fs::read_to_string(&path).unwrap_or_else(|e| panic!("cannot read {}: {}", path, e));
+				(path, content)
+			}).collect::<Vec<_>>();
+			match merge(&fluxes, label, &files) {
+				Ok(lines) => lines.iter().for_each(|line| println!("{}", line)),
+				Err(e) => panic!("{}", e),
 			}
 		},
 		"check" => {
+			assert!(opts.lattice() == Lattice::Square, "{}", HELP);
 			for q in 2.. {
-				let vl = ut.intervals(1, q).0;
+				let vl = ut.intervals(1, q, &opts).0;
 				if vl == q || vl == q - 1 && q % 2 == 0 {
 					if q > 100 || q % 10 == 0 {
 						println!("{} ok", q);
@@ -121,9 +452,10 @@ fn main() {
 			}
 		},
 		"check_full" => {
+			assert!(opts.lattice() == Lattice::Square, "{}", HELP);
 			'qloop: for q in 2.. {
 				for p in (2..=q/2).filter(|x| coprime(*x, q)) {
-					let vl = ut.intervals(p, q).0;
+					let vl = ut.intervals(p, q, &opts).0;
 					if !(vl == q || vl == q - 1 && q % 2 == 0) {
 						println!("{}/{} fail", p, q);
 						break 'qloop
@@ -140,6 +472,162 @@ fn main() {
 	};
 }
 
+// the results f(task) computed by a pool of threads, each taking the next task when done with one,
+// and passed to emit in the order of the tasks as soon as all before them are
+fn for_each_ordered<T, R, F, E>(tasks: &[T], threads: usize, f: F, mut emit: E)
+	where T: Sync, R: Send, F: Fn(&T) -> R + Sync, E: FnMut(R)
+{
+	let next = AtomicUsize::new(0);
+	let (tx, rx) = mpsc::channel();
+	thread::scope(|s| {
+		for _ in 0..threads.min(tasks.len()) {
+			let (tx, next, f) = (tx.clone(), &next, &f);
+			s.spawn(move || loop {
+				let i = next.fetch_add(1, Ordering::Relaxed);
+				if i >= tasks.len() || tx.send((i, f(&tasks[i]))).is_err() {
+					break
+				}
+			});
+		}
+		drop(tx);
+		let mut done = BTreeMap::new();
+		let mut printed = 0;
+		for (i, r) in rx {
+			done.insert(i, r);
+			while let Some(r) = done.remove(&printed) {
+				emit(r);
+				printed += 1;
+			}
+		}
+	});
+}
+
+// the lines label(p, q) + f(p, q) of a sweep over fluxes, on stdout or into the file of --output, which then serves
+// as the checkpoint: every line is written as soon as it is done, and file.checkpoint records the sweep by its key
+// and tells how many lines are done
+fn sweep<L, F>(key: &str, fluxes: &[(usize, usize)], opts: &Options, label: L, f: F) where L: Fn(usize, usize) -> String + Sync, F: Fn(usize, usize) -> String + Sync {
+	let fluxes = &match opts.shard() {
+		Some((i, n)) => fluxes.iter().copied().filter(|&(p, q)| shard_of(p, q, n) == i).collect(),
+		None => fluxes.to_vec(),
+	};
+	let line = |&(p, q): &(usize, usize)| format!("{}{}", label(p, q), f(p, q));
+	let path = match &opts.output {
+		None => return for_each_ordered(fluxes, opts.threads(), line, |line| println!("{}", line)),
+		Some(path) => path,
+	};
+	let checkpoint = format!("{}.checkpoint", path);
+	let done = if opts.resume {
+		let counted = read_checkpoint(&checkpoint, key);
+		let done = resume(path, fluxes, &label);
+// the line after the last one counted may have been written just before the interruption
+		assert!(done == counted || done == counted + 1, "{} has {} complete lines, but {} counts {}", path, done, checkpoint, counted);
+		done
+	} else {0};
+	let file = if opts.resume {fs::OpenOptions::new().append(true).open(path)} else {fs::File::create(path)};
+	let mut file = file.expect("cannot open the output file");
+	fs::write(&checkpoint, format!("{}\n{} done\n", key, done)).expect("cannot write the checkpoint");
+	let mut count = done;
+	for_each_ordered(&fluxes[done..], opts.threads(), line, |line| {
+		writeln!(file, "{}", line).and_then(|_| file.flush()).expect("cannot write the output file");
+		let (p, q) = fluxes[count];
+		count += 1;
+		fs::write(&checkpoint, format!("{}\n{} done, the last {}/{}\n", key, count, p, q)).expect("cannot write the checkpoint");
+	});
+}
+
+// the text that begins the line of p/q in the output of a sweep
+type Label = fn(usize, usize) -> String;
+
+// the fluxes of a sweep command, in the order of its lines, and their label
+fn sweep_fluxes(command: &str, n: usize, period: usize) -> (Vec<(usize, usize)>, Label) {
+	match command {
+		"trq_upto" => {
+			let fluxes = (1..=n).flat_map(|q| (1..=q*period/2).filter(move |x| coprime(*x, q)).map(move |p| (p, q))).collect();
+			(fluxes, |p, q| format!("[{}, {}] -> ", p, q))
+		},
+		"intervals_upto" => {
+			let fluxes = (1..=n).flat_map(|q| (0..=q*period/2).filter(move |x| coprime(*x, q)).map(move |p| (p, q))).collect();
+			(fluxes, |p, q| format!("{}/{}: ", p, q))
+		},
+		"intervals_farey" => {
+			let right = if period.is_multiple_of(2) {(period / 2, 1)} else {(period, 2)};
+			(farey(n, right), |p, q| format!("{}/{}: ", p, q))
+		},
+		_ => panic!("{}", HELP),
+	}
+}
+
+// the shard of p/q among n, from 1 to n, by a multiplicative hash that does not depend on the sweep or the platform
+fn shard_of(p: usize, q: usize, n: usize) -> usize {
+	let h = ((p as u64) << 32 ^ q as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 32;
+	(h % n as u64) as usize + 1
+}
+
+// the lines of the files (path, content) of the shards of a sweep over fluxes, in its order; a flux may appear in
+// several files if its lines are the same, and every flux must appear, as must the newline that ends every file
+fn merge(fluxes: &[(usize, usize)], label: Label, files: &[(String, String)]) -> Result<Vec<String>, String> {
+	let index = fluxes.iter().enumerate().map(|(i, &(p, q))| (label(p, q), i)).collect::<std::collections::HashMap<_, _>>();
+// what follows the numbers in a label, e.g. ": " in "1/3: "
+	let end = label(0, 1);
+	let end = &end[end.rfind(|c: char| c.is_ascii_digit()).unwrap() + 1..];
+// the first line of every flux, with its file and line number
+	let mut lines: Vec<Option<(&str, &str, usize)>> = vec![None; fluxes.len()];
+	for (path, content) in files {
+		if !content.is_empty() && !content.ends_with('\n') {
+			return Err(format!("the last line of {} is incomplete", path))
+		}
+		for (n, line) in content.lines().enumerate() {
+			let i = line.find(end).and_then(|k| index.get(&line[..k + end.len()]));
+			let i = *i.ok_or_else(|| format!("line {} of {} is not for a flux of the sweep", n + 1, path))?;
+			match lines[i] {
+				Some((other, first, m)) if other != line => {
+					let (p, q) = fluxes[i];
+					return Err(format!("the lines for {}/{} differ, at line {} of {} and at line {} of {}", p, q, m, first, n + 1, path))
+				},
+				Some(_) => {},
+				None => lines[i] = Some((line, path, n + 1)),
+			}
+		}
+	}
+	let missing = fluxes.iter().zip(&lines).filter(|(_, l)| l.is_none()).map(|((p, q), _)| format!("{}/{}", p, q)).collect::<Vec<_>>();
+	if !missing.is_empty() {
+		return Err(format!("{} fluxes are missing: {}", missing.len(), missing.join(", ")))
+	}
+	Ok(lines.into_iter().map(|l| l.unwrap().0.to_string()).collect())
+}
+
+// the number of lines done according to the checkpoint, which must be that of the sweep key
+fn read_checkpoint(path: &str, key: &str) -> usize {
+	let content = fs::read_to_string(path).unwrap_or_else(|e| panic!("cannot read {}: {}", path, e));
+	let mut lines = content.lines();
+	let recorded = lines.next().unwrap_or_default();
+	assert!(recorded == key, "{} is for the sweep\n{}\nnot for\n{}", path, recorded, key);
+	lines.next().and_then(|l| l.split(' ').next()?.parse().ok()).unwrap_or_else(|| panic!("{} does not tell how many lines are done", path))
+}
+
+// the number of complete lines of the output file, each checked to begin with the label of its flux;
+// an incomplete last line, cut off by the interruption, is removed
+fn resume<L: Fn(usize, usize) -> String>(path: &str, fluxes: &[(usize, usize)], label: &L) -> usize {
+	let content = fs::read_to_string(path).expect("cannot read the output file to resume");
+	let complete = content.rfind('\n').map_or(0, |i| i + 1);
+	let lines = content[..complete].lines().collect::<Vec<_>>();
+	assert!(lines.len() <= fluxes.len(), "{} has more lines than the sweep", path);
+	for (i, (line, &(p, q))) in lines.iter().zip(fluxes).enumerate() {
+		assert!(line.starts_with(&label(p, q)), "line {} of {} is not for {}/{}", i + 1, path, p, q);
+	}
+	if complete < content.len() {
+		fs::OpenOptions::new().write(true).open(path).and_then(|file| file.set_len(complete as u64)).expect("cannot truncate the output file");
+	}
+	lines.len()
+}
+
+fn fraction(s: &str) -> (i64, i64) {
+	match s.split_once('/') {
+		Some((n, d)) => (n.parse().expect(HELP), d.parse().expect(HELP)),
+		None => (s.parse().expect(HELP), 1),
+	}
+}
+
 pub fn coprime(p: usize, q: usize) -> bool {
 	let (mut l, mut m) = (p, q);
 	while l > 0 {
@@ -150,8 +638,7 @@ pub fn coprime(p: usize, q: usize) -> bool {
 	m == 1
 }
 
-pub fn farey(n: usize) -> Vec<(usize, usize)> {
-	let right = (1, 2);
+pub fn farey(n: usize, right: (usize, usize)) -> Vec<(usize, usize)> {
 	let mut f = vec![(0, 1), right];
 //	let mut fseq = f.clone();
 	for _ in 0..n {
@@ -192,8 +679,120 @@ mod tests {
 		assert!(coprime(1024, 61));
 	}
 	
+	#[test]
+	fn ordered() {
+		let tasks = (0..40u64).collect::<Vec<_>>();
+		let mut out = Vec::new();
+		// the tasks finish out of order
+		for_each_ordered(&tasks, 4, |&i| {
+			thread::sleep(std::time::Duration::from_millis((40 - i) % 7));
+			i * i
+		}, |r| out.push(r));
+		assert_eq!(out, tasks.iter().map(|i| i * i).collect::<Vec<_>>());
+	}
+	
+	#[test]
+	fn resume_test() {
+		let path = std::env::temp_dir().join(format!("hofstadter_resume_test_{}", std::process::id()));
+		let path = path.to_str().unwrap();
+		let fluxes = [(0, 1), (1, 2), (1, 3), (2, 3)];
+		let label = |p, q| format!("{}/{}: ", p, q);
+		fs::write(path, "0/1: a\n1/2: b\n1/3: c, d").unwrap();
+		assert_eq!(resume(path, &fluxes, &label), 2);
+		assert_eq!(fs::read_to_string(path).unwrap(), "0/1: a\n1/2: b\n");
+		assert_eq!(resume(path, &fluxes, &label), 2);
+		fs::write(path, "0/1: a\n1/3: c\n").unwrap();
+		assert!(std::panic::catch_unwind(|| resume(path, &fluxes, &label)).is_err());
+		fs::remove_file(path).unwrap();
+	}
+	
+	#[test]
+	fn checkpoint() {
+		let path = std::env::temp_dir().join(format!("hofstadter_checkpoint_test_{}", std::process::id()));
+		let path = path.to_str().unwrap();
+		let opts = |lambda: &str| Options {
+			lambda: Some(lambda.to_string()),
+			..Options::default()
+		};
+		let key = opts("2").sweep_key("intervals_upto", 10, UnderlyingType::F64);
+		fs::write(path, format!("{}\n3 done, the last 1/3\n", key)).unwrap();
+		assert_eq!(read_checkpoint(path, &key), 3);
+		// another λ, type or shard is another sweep
+		let other = opts("1").sweep_key("intervals_upto", 10, UnderlyingType::F64);
+		assert!(std::panic::catch_unwind(|| read_checkpoint(path, &other)).is_err());
+		let other = opts("2").sweep_key("intervals_upto", 10, UnderlyingType::BigDecimal);
+		assert!(std::panic::catch_unwind(|| read_checkpoint(path, &other)).is_err());
+		let sharded = Options {
+			shard: Some("1/2".to_string()),
+			..opts("2")
+		};
+		assert!(std::panic::catch_unwind(|| read_checkpoint(path, &sharded.sweep_key("intervals_upto", 10, UnderlyingType::F64))).is_err());
+		fs::remove_file(path).unwrap();
+	}
+	
+	#[test]
+	fn shards() {
+		let (fluxes, label) = sweep_fluxes("intervals_upto", 30, 2);
+		let shards = (1..=3).map(|i| fluxes.iter().filter(|&&(p, q)| shard_of(p, q, 3) == i).map(|&(p, q)| format!("{}x\n", label(p, q))).collect::<String>()).collect::<Vec<_>>();
+		assert!(shards.iter().all(|s| s.lines().count() > fluxes.len() / 5));
+		let file = |i: usize| (format!("shard {}", i), shards[i].clone());
+		let all = fluxes.iter().map(|&(p, q)| format!("{}x", label(p, q))).collect::<Vec<_>>();
+		assert_eq!(merge(&fluxes, label, &[file(2), file(0), file(1)]), Ok(all.clone()));
+		// a flux computed twice is kept once
+		assert_eq!(merge(&fluxes, label, &[file(2), file(0), file(1), file(0)]), Ok(all));
+		assert!(merge(&fluxes, label, &[file(0), file(1)]).unwrap_err().contains("missing"));
+		let other = ("other".to_string(), "1/3: y\n".to_string());
+		let n = shards[shard_of(1, 3, 3) - 1].lines().position(|l| l.starts_with("1/3: ")).unwrap() + 1;
+		let differ = format!("the lines for 1/3 differ, at line {} of shard {} and at line 1 of other", n, shard_of(1, 3, 3) - 1);
+		assert_eq!(merge(&fluxes, label, &[file(0), file(1), file(2), other]), Err(differ));
+		let foreign = ("foreign".to_string(), "1/31: x\n".to_string());
+		assert!(merge(&fluxes, label, &[file(0), file(1), file(2), foreign]).is_err());
+		let cut = ("cut".to_string(), "1/3: ".to_string());
+		assert!(merge(&fluxes, label, &[file(0), file(1), file(2), cut]).is_err());
+		let (fluxes, label) = sweep_fluxes("trq_upto", 5, 2);
+		let lines = fluxes.iter().map(|&(p, q)| format!("{}[1, 2],", label(p, q))).collect::<Vec<_>>();
+		assert_eq!(merge(&fluxes, label, &[("all".to_string(), lines.join("\n") + "\n")]), Ok(lines));
+	}
+	
+	#[test]
+	fn isolation() {
+		let opts = |lattice: &str, diagonal: &str, isolation: &str| Options {
+			lattice: Some(lattice.to_string()),
+			diagonal: Some(diagonal.to_string()),
+			isolation: Some(isolation.to_string()),
+			..Options::default()
+		};
+		for (lattice, diagonal) in [("square", "0"), ("square", "0.2"), ("honeycomb", "0"), ("triangular", "0")] {
+			let (sturm, descartes) = (opts(lattice, diagonal, "sturm"), opts(lattice, diagonal, "descartes"));
+			for (p, q) in [(1, 3), (2, 5), (3, 8), (4, 11)] {
+				let (s, d) = (intervals::<f64>(p, q, 0, &sturm).unwrap(), intervals::<f64>(p, q, 0, &descartes).unwrap());
+				assert!(s.len() == d.len() && s.iter().zip(&d).all(|(a, b)| (a.0 - b.0).abs() < 1e-12 && (a.1 - b.1).abs() < 1e-12), "{} {}/{}", lattice, p, q);
+				let accu = q as i64 * 3 / 4 + 2;
+				assert_eq!(intervals_auto::<BigDecimal>(p, q, accu, &sturm), intervals_auto::<BigDecimal>(p, q, accu, &descartes));
+			}
+		}
+	}
+	
+	#[test]
+	fn cached() {
+		let dir = std::env::temp_dir().join(format!("hofstadter_cached_test_{}", std::process::id()));
+		let opts = Options {
+			cache: Some(ResultCache::new(&dir).unwrap()),
+			..Options::default()
+		};
+		assert_eq!(opts.cached("trq", UnderlyingType::BigDecimal, 1, 8, || "a".to_string()), "a");
+		assert_eq!(opts.cached("trq", UnderlyingType::BigDecimal, 1, 8, || unreachable!()), "a");
+		assert_eq!(opts.cached("trq", UnderlyingType::F64, 1, 8, || "b".to_string()), "b");
+		// the keys hold the accuracy and the version of the algorithms
+		let keys = fs::read_dir(&dir).unwrap().map(|e| fs::read_to_string(e.unwrap().path()).unwrap().lines().nth(1).unwrap().to_string()).collect::<Vec<_>>();
+		assert!(keys.iter().any(|k| k.contains(&format!("BigDecimal 1/8 accu=8 algorithm={} ", ALGORITHM))));
+		assert!(keys.iter().any(|k| k.contains(&format!("F64 1/8 accu=0 algorithm={} ", ALGORITHM))));
+		fs::remove_dir_all(&dir).unwrap();
+	}
+	
 	#[test]
 	fn farey_test() {
-		assert_eq!(farey(4), vec![(0, 1), (1, 2), (1, 3), (1, 4), (1, 5), (2, 5), (1, 6), (2, 7), (3, 7), (3, 8), (2, 9), (4, 9), (3, 10), (3, 11), (4, 11), (5, 12), (5, 13)]);
+		assert_eq!(farey(4, (1, 2)), vec![(0, 1), (1, 2), (1, 3), (1, 4), (1, 5), (2, 5), (1, 6), (2, 7), (3, 7), (3, 8), (2, 9), (4, 9), (3, 10), (3, 11), (4, 11), (5, 12), (5, 13)]);
+		assert_eq!(farey(1, (1, 1)), vec![(0, 1), (1, 1), (1, 2)]);
 	}
 }
@@ -1,4 +1,6 @@
-use bigdecimal::{BigDecimal, ToPrimitive};
+use bigdecimal::{BigDecimal, ToPrimitive, Zero};
+
+use hofstadter_butterfly::Lattice;
 
 use line_drawing::Supercover as Line;
 
@@ -6,6 +8,7 @@ use std::{
 	io::{stdin, prelude::*},
 	str::FromStr,
 	fs::File,
+	collections::BTreeMap,
 };
 
 enum Format {
@@ -15,18 +18,81 @@ enum Format {
 
 use Format::*;
 
+const USAGE: &str = "Usage:
+cat out.txt | cargo run --release --bin txt2img [png|svg] [square|honeycomb|triangular] [--palette <p>] [--sigma <n>] [--wannier]
+Output of main's --wannier is drawn as the Wannier diagram with --wannier.
+Gaps labelled with main's --gaps are colored by their Hall conductance σ, -n <= σ <= n (default 6),
+with a diverging palette 'rdbu' (default), 'puor', 'brbg' or a list of colors like '#b2182b,#f7f7f7,#2166ac'";
+
+// diverging palette from σ = -range to σ = range, linearly interpolated between the stops
+struct Palette {
+	stops: Vec<[u8; 3]>,
+	range: i64,
+}
+
+impl Palette {
+	fn new(name: &str, range: i64) -> Result<Self, String> {
+		let stops = match name {
+			"rdbu" => "#b2182b,#f7f7f7,#2166ac",
+			"puor" => "#b35806,#f7f7f7,#542788",
+			"brbg" => "#8c510a,#f5f5f5,#01665e",
+			_ => name,
+		};
+		let stops = stops.split(',').map(|c| {
+			let c = c.trim().trim_start_matches('#');
+			if c.len() != 6 {
+				return Err(format!("Bad color '{}'", c))
+			}
+			let channel = |i: usize| u8::from_str_radix(&c[2*i..2*i+2], 16).map_err(|e| e.to_string());
+			Ok([channel(0)?, channel(1)?, channel(2)?])
+		}).collect::<Result<Vec<_>, _>>()?;
+		if stops.len() < 2 {
+			return Err("A palette needs at least two colors".into())
+		}
+		if range < 0 {
+			return Err(format!("Bad range of σ {}", range))
+		}
+		Ok(Palette {
+			stops,
+			range,
+		})
+	}
+
+	fn color(&self, sigma: i64) -> Option<[u8; 3]> {
+		if sigma.abs() > self.range {
+			return None
+		}
+		let t = if self.range == 0 {0.5} else {(sigma + self.range) as f64 / (2 * self.range) as f64};
+		let t = t * (self.stops.len() - 1) as f64;
+		let i = (t as usize).min(self.stops.len() - 2);
+		let (a, b, f) = (self.stops[i], self.stops[i+1], t - i as f64);
+		let mix = |k: usize| (a[k] as f64 + (b[k] as f64 - a[k] as f64) * f).round() as u8;
+		Some([mix(0), mix(1), mix(2)])
+	}
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
 	let mut args = std::env::args().skip(1);
-	let format = match args.next().unwrap_or(String::new()).as_ref() {
+	let format = match args.next().unwrap_or_default().as_ref() {
 		"png" => Png,
 		"svg" => Svg,
 		_ => {
-			println!("Usage:
-cat out.txt | cargo run --release --bin txt2img [png|svg]");
+			println!("{}", USAGE);
 			return Ok(())
 		}
 	};
-	
+	let (mut lattice, mut palette, mut range, mut wannier) = (Lattice::Square, "rdbu".to_string(), 6, false);
+	while let Some(arg) = args.next() {
+		match arg.as_ref() {
+			"--palette" => palette = args.next().ok_or(USAGE)?,
+			"--sigma" => range = args.next().ok_or(USAGE)?.parse()?,
+			"--wannier" => wannier = true,
+			_ => lattice = arg.parse()?,
+		}
+	}
+	let period = lattice.flux_period() as u32;
+	let palette = Palette::new(&palette, range)?;
+
 	let s = stdin();
 	let mut v = Vec::new();
 	for line in s.lock().lines() {
@@ -37,65 +103,162 @@ cat out.txt | cargo run --release --bin txt2img [png|svg]");
 		let t = frac.split("/").collect::<Vec<&str>>();
 		assert!(t.len() == 2);
 		let (num, denom) = (t[0].parse::<u32>()?, t[1].parse::<u32>()?);
-		let intervals = intervals.split(", ").map(|int| {
+// gap labels σ:s follow the intervals after " | ", '?' if the gaps could not be labelled
+		let (intervals, sigmas) = match intervals.split_once(" |") {
+			Some((intervals, _)) if wannier => (intervals, None),
+			Some((intervals, labels)) if labels.trim() != "?" => {
+				let sigmas = labels.split(", ").map(str::trim).filter(|l| !l.is_empty()).map(|l| {
+					l.split(':').next().unwrap().parse::<i64>()
+				}).collect::<Result<Vec<_>, _>>()?;
+				(intervals, Some(sigmas))
+			},
+			Some((intervals, _)) => (intervals, None),
+			None => (intervals, None),
+		};
+// in the Wannier diagram every gap is a point r/q, drawn as an interval of zero length
+		let intervals = if wannier {
+			intervals.split(", ").filter(|n| !n.is_empty() && *n != "?").map(|n| {
+				let t = n.split("/").collect::<Vec<&str>>();
+				assert!(t.len() == 2);
+				let n = (BigDecimal::from_str(t[0]).unwrap() / BigDecimal::from_str(t[1]).unwrap()).with_prec(12);
+				(n.clone(), n)
+			}).collect::<Vec<_>>()
+		} else {intervals.split(", ").map(|int| {
 			let t = int.split("..").collect::<Vec<&str>>();
 			assert!(t.len() == 2);
 			let (l, r) = (BigDecimal::from_str(t[0]).unwrap(), BigDecimal::from_str(t[1]).unwrap());
 			(l, r)
-		}).collect::<Vec<_>>();
-		v.push(((num, denom), intervals.clone()));
-		if denom != num * 2 {
-			v.push(((denom - num, denom), intervals));
+		}).collect::<Vec<_>>()};
+		if let Some(sigmas) = &sigmas {
+			assert!(sigmas.len() + 1 == intervals.len());
+		}
+		v.push(((num, denom), intervals.clone(), sigmas.clone()));
+// the mirror image p/q -> period - p/q reverses the sign of σ
+		if denom * period != num * 2 {
+			v.push(((denom * period - num, denom), intervals, sigmas.map(|s| s.into_iter().map(|x| -x).collect())));
 		}
 	}
+	let colored = v.iter().any(|(_, _, sigmas)| sigmas.is_some());
+// the energy axis spans [-half, half]: [-4, 4] unless some band edge is further out;
+// the density of states spans [0, 1], or [0, 2] with two sites per unit cell
+	let half = v.iter().flat_map(|(_, int, _)| int.iter().flat_map(|(l, r)| [l.abs(), r.abs()]))
+		.map(|x| (x + BigDecimal::from_str("0.999").unwrap()).with_scale(0))
+		.fold(BigDecimal::from(if wannier {1} else {4}), |a, x| if x > a {x} else {a});
+	let (low, full) = if wannier {(BigDecimal::from(0), half)} else {(-half.clone(), BigDecimal::from(2) * &half)};
 	match format {
 		Png => {
 			let n: u32 = 4096;
 			let color = [0, 0, 0, 255];
 			let mut pixels = vec![255; (n * n * 4) as usize];
-			for ((num, denom), intervals) in v {
-				let x = ((num * n) as f64 / denom as f64) as i32;
-				for (y1, y2) in intervals {
-					let y1: BigDecimal = (y1 + BigDecimal::from(4)) / 8 * BigDecimal::from(n);
-					let y1 = y1.to_i32().unwrap();
-					let y2: BigDecimal = (y2 + BigDecimal::from(4)) / 8 * BigDecimal::from(n);
-					let y2 = y2.to_i32().unwrap();
+			let to_px = |y: &BigDecimal| {
+				let y: BigDecimal = (y - &low) / &full * BigDecimal::from(n);
+				y.to_i32().unwrap()
+			};
+			for ((num, denom), intervals, sigmas) in v {
+				let x = ((num * n) as f64 / (denom * period) as f64) as i32;
+				let mut draw = |y1: i32, y2: i32, color: &[u8; 4]| {
 					let line = Line::new((x, y1), (x, y2));
 					for (x, y) in line {
 						let x = if x >= n as i32 { x-1 } else {x};
-						let x = if x < 0 as i32 { 0 } else {x};
+						let x = if x < 0_i32 { 0 } else {x};
 						let y = if y >= n as i32 { y-1 } else {y};
-						let y = if y < 0 as i32 { 0 } else {y};
+						let y = if y < 0_i32 { 0 } else {y};
 						let p = (n as usize * x as usize + y as usize) * 4;
-						pixels[p..p+4].copy_from_slice(&color);
+						pixels[p..p+4].copy_from_slice(color);
 					}
+				};
+				for ((_, y1), ((y2, _), sigma)) in intervals.iter().zip(intervals.iter().skip(1).zip(sigmas.unwrap_or_default())) {
+					if let (Some([r, g, b]), true) = (palette.color(sigma), y2 > y1) {
+						draw(to_px(y1), to_px(y2), &[r, g, b, 255]);
+					}
+				}
+				let dot = if wannier {1} else {0};
+				for (y1, y2) in intervals {
+					draw(to_px(&y1) - dot, to_px(&y2) + dot, &color);
 				}
 			}
 			repng::encode(File::create("out.png")?, n, n, &pixels)?;
 		},
 		Svg => {
 			let (width, height) = (600, 450);
+			let legend = if colored {80} else {0};
+// a legend entry per σ, at least a pixel high
+			let step = ((height - 20) as i64 / (2 * palette.range + 1)).min(14);
+			if colored && step < 1 {
+				return Err(format!("The legend of --sigma {} does not fit in an image {} pixels high", palette.range, height).into())
+			}
 			let mut f = File::create("out.svg")?;
 			writeln!(f, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
-			writeln!(f, r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#, width, height)?;
-			writeln!(f, r#"<g fill="transparent" stroke="black" stroke-width="0.5" stroke-linecap="square">"#)?;
-			for ((num, denom), intervals) in v {
+			writeln!(f, r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">"#, width + legend, height)?;
+			let to_svg = |y: &BigDecimal| {
+				let y: BigDecimal = (y - &low) / &full * BigDecimal::from(width);
+				let prec = 38;
+				if y.digits() > prec {y.with_prec(prec)} else {y}
+			};
+			if colored {
+				let mut gaps = BTreeMap::new();
+				for ((num, denom), intervals, sigmas) in &v {
+					let x = (num * height) as f32 / (denom * period) as f32;
+					let x = if x == 0.0 {1.0} else {x};
+					for ((_, y1), ((y2, _), sigma)) in intervals.iter().zip(intervals.iter().skip(1).zip(sigmas.iter().flatten())) {
+						if palette.color(*sigma).is_some() && y2 > y1 {
+							let w: BigDecimal = (y2 - y1) / &full * BigDecimal::from(width);
+							let d: &mut String = gaps.entry(*sigma).or_default();
+							d.push_str(&format!("M {} {} h {} ", to_svg(y1), x, w.with_prec(5)));
+						}
+					}
+				}
+				writeln!(f, r#"<g fill="transparent" stroke-width="0.5" stroke-linecap="square">"#)?;
+				for (sigma, d) in gaps {
+					let [r, g, b] = palette.color(sigma).unwrap();
+					writeln!(f, r##"<path id="sigma_{}" stroke="#{:02x}{:02x}{:02x}" d="{}"/>"##, sigma, r, g, b, d)?;
+				}
+				writeln!(f, "</g>")?;
+			}
+			let stroke = if wannier {r#"stroke-width="1.5" stroke-linecap="round""#} else {r#"stroke-width="0.5" stroke-linecap="square""#};
+			writeln!(f, r#"<g fill="transparent" stroke="black" {}>"#, stroke)?;
+			for ((num, denom), intervals, _) in v {
 				write!(f, r#"<path id="{}_{}" d=""#, num, denom)?;
-				let x = (num * height) as f32 / denom as f32;
+				let x = (num * height) as f32 / (denom * period) as f32;
 				let x = if x == 0.0 {1.0} else {x};
 				for (y1, y2) in intervals {
-					let w: BigDecimal = (y2 - &y1) / 8 * BigDecimal::from(width);
-					let w = w.with_prec(5);
-					let y1: BigDecimal = (y1 + BigDecimal::from(4)) / 8 * BigDecimal::from(width);
-					let prec = 38;
-					let y1 = if y1.digits() > prec {y1.with_prec(prec)} else {y1};
-					write!(f, "M {} {} h {} ", y1, x, w)?;
+					let w: BigDecimal = (y2 - &y1) / &full * BigDecimal::from(width);
+					let w = if w.is_zero() {w.with_scale(0)} else {w.with_prec(5)};
+					write!(f, "M {} {} h {} ", to_svg(&y1), x, w)?;
 				}
 				writeln!(f, "\"/>")?;
 			}
 			writeln!(f, "</g>")?;
+			if colored {
+				writeln!(f, r#"<g id="legend" font-family="sans-serif" font-size="10">"#)?;
+				for (i, sigma) in (-palette.range..=palette.range).rev().enumerate() {
+					let [r, g, b] = palette.color(sigma).unwrap();
+					let y = 10 + i as i64 * step;
+					writeln!(f, r##"<rect x="{}" y="{}" width="20" height="{}" fill="#{:02x}{:02x}{:02x}"/>"##, width + 10, y, step, r, g, b)?;
+					writeln!(f, r#"<text x="{}" y="{}" dominant-baseline="middle">σ = {}</text>"#, width + 35, y as f64 + step as f64 / 2.0, sigma)?;
+				}
+				writeln!(f, "</g>")?;
+			}
 			writeln!(f, "</svg>")?;
 		},
 	}
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn palette() {
+		let p = Palette::new("#000000,#ffffff,#0000ff", 2).unwrap();
+		assert_eq!(p.color(-2), Some([0, 0, 0]));
+		assert_eq!(p.color(-1), Some([128, 128, 128]));
+		assert_eq!(p.color(0), Some([255, 255, 255]));
+		assert_eq!(p.color(2), Some([0, 0, 255]));
+		assert_eq!(p.color(3), None);
+		assert_eq!(Palette::new("rdbu", 0).unwrap().color(0), Some([247, 247, 247]));
+		assert!(Palette::new("#0000ff", 1).is_err());
+		assert!(Palette::new("blue,red", 1).is_err());
+	}
+}
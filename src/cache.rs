@@ -0,0 +1,79 @@
+use std::{fs, io, path::PathBuf, process, thread};
+
+// computed results on disk, one file per key, named by the FNV-1a hash of the key and holding the crate version,
+// the key and the result on separate lines; a file of another version or for another key (a hash collision)
+// is a miss, and is overwritten by the next result for the key
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+	dir: PathBuf,
+}
+
+const VERSION: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
+
+fn fnv1a(s: &str) -> u64 {
+	s.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| (h ^ b as u64).wrapping_mul(0x100_0000_01b3))
+}
+
+impl ResultCache {
+	pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+		let dir = dir.into();
+		fs::create_dir_all(&dir)?;
+		Ok(ResultCache {
+			dir,
+		})
+	}
+
+	fn path(&self, key: &str) -> PathBuf {
+		self.dir.join(format!("{:016x}.txt", fnv1a(key)))
+	}
+
+	pub fn get(&self, key: &str) -> Option<String> {
+		let content = fs::read_to_string(self.path(key)).ok()?;
+		let mut lines = content.splitn(3, '\n');
+		if lines.next()? != VERSION || lines.next()? != key {
+			return None
+		}
+		lines.next().map(String::from)
+	}
+
+// written to a temporary file first and renamed, so that a reader, another thread or process, never sees a partial entry
+	pub fn put(&self, key: &str, value: &str) -> io::Result<()> {
+		assert!(!key.contains('\n'), "cache keys are single lines");
+		let path = self.path(key);
+		let thread = format!("{:?}", thread::current().id()).replace(|c: char| !c.is_ascii_digit(), "");
+		let tmp = path.with_extension(format!("{}.{}.tmp", process::id(), thread));
+		fs::write(&tmp, format!("{}\n{}\n{}", VERSION, key, value))?;
+		fs::rename(&tmp, &path)
+	}
+
+// the cached result for key, or f() stored under it; failing to store it only costs the next run the computation
+	pub fn get_or_insert_with<F: FnOnce() -> String>(&self, key: &str, f: F) -> String {
+		if let Some(value) = self.get(key) {
+			return value
+		}
+		let value = f();
+		let _ = self.put(key, &value);
+		value
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cache() {
+		let dir = std::env::temp_dir().join(format!("hofstadter_cache_test_{}", process::id()));
+		let cache = ResultCache::new(&dir).unwrap();
+		assert_eq!(cache.get("intervals 1/3"), None);
+		assert_eq!(cache.get_or_insert_with("intervals 1/3", || "a\nb".to_string()), "a\nb");
+		assert_eq!(cache.get_or_insert_with("intervals 1/3", || unreachable!()), "a\nb");
+		assert_eq!(cache.get("intervals 2/3"), None);
+		// an entry of another version is stale
+		fs::write(cache.path("trq 1/3"), format!("{} 0.0.0\ntrq 1/3\nx", env!("CARGO_PKG_NAME"))).unwrap();
+		assert_eq!(cache.get("trq 1/3"), None);
+		cache.put("trq 1/3", "y").unwrap();
+		assert_eq!(cache.get("trq 1/3").as_deref(), Some("y"));
+		fs::remove_dir_all(&dir).unwrap();
+	}
+}
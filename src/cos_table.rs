@@ -0,0 +1,92 @@
+use crate::{Zero, Appr, BigDecimal, cos_enclosure};
+use std::sync::{Arc, Mutex};
+
+// the tables used most recently, the latest first: (q, accu, 2cos(πp/q) with its error bound for 0 <= p <= q/2)
+type Table = Arc<Vec<(BigDecimal, BigDecimal)>>;
+static CACHE: Mutex<Vec<(u32, i64, Table)>> = Mutex::new(Vec::new());
+const CACHED_TABLES: usize = 64;
+
+// 2cos(πp/q) for 0 <= p <= q/2 rounded to accu digits, each with a bound on its error below eps(accu),
+// from the cache if it holds them; the sweeps over p/q with the same q and accu then compute them once
+// (computed without holding the lock, so that threads needing other tables do not wait)
+pub fn get(q: u32, accu: i64) -> Table {
+	{
+		let mut cache = CACHE.lock().unwrap();
+		if let Some(i) = cache.iter().position(|(n, a, _)| *n == q && *a == accu) {
+			let entry = cache.remove(i);
+			let table = entry.2.clone();
+			cache.insert(0, entry);
+			return table
+		}
+	}
+	let table = Arc::new(compute(q, accu));
+	let mut cache = CACHE.lock().unwrap();
+	cache.insert(0, (q, accu, table.clone()));
+	cache.truncate(CACHED_TABLES);
+	table
+}
+
+// 2cos(πp/q) by the Chebyshev recurrence x_(p+1) = x_1·x_p - x_(p-1), rounded to w digits, from an enclosure of x_1:
+// an error e in x_1 and the roundings, δ each, add up to at most p^2·(e + δ/2), as the derivative of 2cos(p·arccos(x/2))
+// and the solutions sin(pθ)/sin(θ) of the recurrence are bounded by p^2 and by p; the guard digits cover this factor,
+// and are doubled should the errors still not be below eps(accu)
+fn compute(q: u32, accu: i64) -> Vec<(BigDecimal, BigDecimal)> {
+	let accu = accu.max(0);
+	let n = (q / 2) as usize;
+	let exact = |p: u32| if p == 0 {Some(2)} else if 2 * p == q {Some(0)} else if 3 * p == q {Some(1)} else {None};
+	if q < 3 {
+		return (0..=n as u32).map(|p| (BigDecimal::from(exact(p).unwrap()), BigDecimal::zero())).collect()
+	}
+	let mut guard = (q as u64 * q as u64).to_string().len() as i64 + 1;
+	loop {
+		let w = accu + guard;
+// the error of the enclosure grows about 4 times with every doubling
+		let k = ((w as f64).sqrt() / 2.0).ceil() as u32;
+		let (lo, hi) = cos_enclosure(1, q, (w + k as i64) as usize, k);
+		let (lo, hi) = (BigDecimal::new(lo * 2, w + k as i64), BigDecimal::new(hi * 2, w + k as i64));
+		let mid = (lo.clone() + hi.clone()).half();
+		let x1 = mid.clone().accu(w);
+		let e = (hi - lo).half() + (mid - x1.clone()).abs() + BigDecimal::eps(w).half();
+		let mut x = vec![BigDecimal::from(2), x1.clone()];
+		while x.len() <= n {
+			let l = x.len();
+			let next = (x1.clone() * x[l-1].clone()).accu(w) - x[l-2].clone();
+			x.push(next);
+		}
+		let table = x.into_iter().enumerate().map(|(p, x)| match exact(p as u32) {
+			Some(c) => (BigDecimal::from(c), BigDecimal::zero()),
+			None => {
+				let c = x.clone().accu(accu);
+				let err = (BigDecimal::from((p * p) as i64) * e.clone() + (x - c.clone()).abs()).accu(w) + BigDecimal::eps(w);
+				(c, err)
+			},
+		}).collect::<Vec<_>>();
+		if table.iter().all(|(_, err)| *err <= BigDecimal::eps(accu)) {
+			return table
+		}
+		guard *= 2;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn table() {
+		for &(q, accu) in &[(1, 5), (2, 5), (7, 30), (12, 20), (60, 100), (61, 3)] {
+			let t = get(q, accu);
+			assert_eq!(t.len(), q as usize / 2 + 1);
+			for (p, (c, err)) in t.iter().enumerate() {
+				assert!(*err <= BigDecimal::eps(accu), "{}/{}", p, q);
+				if p > 0 && 2 * p < q as usize {
+					let w = accu + 10;
+					let (lo, hi) = cos_enclosure(p as u32, q, w as usize, 4);
+					let (lo, hi) = (BigDecimal::new(lo * 2, w), BigDecimal::new(hi * 2, w));
+					assert!(lo - err.clone() <= *c && *c <= hi + err.clone(), "{}/{}", p, q);
+				}
+			}
+		}
+		assert!(Arc::ptr_eq(&get(60, 100), &get(60, 100)));
+	}
+}
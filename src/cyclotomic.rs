@@ -0,0 +1,568 @@
+use crate::{Zero, One, Signed, Num, BigDecimal};
+use crate::polynomial::Polynomial;
+use crate::matrix2x2::Matrix;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use std::{ops, fmt, cmp::Ordering, sync::Arc};
+
+// the real cyclotomic field Q(c), c = 2cos(2π/n): psi is the minimal polynomial of c (monic, lowest coefficient first)
+#[derive(Debug, PartialEq)]
+pub struct Field {
+	n: usize,
+	psi: Vec<BigInt>,
+}
+
+// the cyclotomic polynomial, (x^n - 1) divided by those of the proper divisors of n
+fn cyclotomic_polynomial(n: usize) -> Vec<BigInt> {
+	let mut p = vec![BigInt::zero(); n + 1];
+	p[0] = -BigInt::one();
+	p[n] = BigInt::one();
+	for d in (1..n).filter(|d| n.is_multiple_of(*d)) {
+		let f = cyclotomic_polynomial(d);
+		let mut quotient = vec![BigInt::zero(); p.len() - f.len() + 1];
+		for i in (0..quotient.len()).rev() {
+			let a = p[i + f.len() - 1].clone();
+			for (j, fj) in f.iter().enumerate() {
+				p[i + j] -= &a * fj;
+			}
+			quotient[i] = a;
+		}
+		p = quotient;
+	}
+	p
+}
+
+impl Field {
+// Φ_n(x) = x^d ψ(x + 1/x), d = φ(n)/2, since Φ_n is palindromic; x^k + x^-k is the Dickson polynomial D_k(c)
+	pub fn new(n: usize) -> Self {
+		assert!(n > 2, "2cos(2π/n) is rational for n <= 2");
+		let phi = cyclotomic_polynomial(n);
+		let d = (phi.len() - 1) / 2;
+		let mut psi = vec![BigInt::zero(); d + 1];
+		psi[0] = phi[d].clone();
+		let dickson = Field::dickson(d);
+		for k in 1..=d {
+			for (i, a) in dickson[k].iter().enumerate() {
+				psi[i] += &phi[d + k] * a;
+			}
+		}
+		Field {
+			n,
+			psi,
+		}
+	}
+
+// D_0 = 2, D_1 = c, D_(k+1) = cD_k - D_(k-1), so that D_k(x + 1/x) = x^k + x^-k
+	fn dickson(d: usize) -> Vec<Vec<BigInt>> {
+		let mut v = vec![vec![BigInt::from(2)], vec![BigInt::zero(), BigInt::one()]];
+		for k in 2..=d {
+			let mut next = vec![BigInt::zero(); k + 1];
+			for (i, a) in v[k-1].iter().enumerate() {
+				next[i + 1] += a;
+			}
+			for (i, a) in v[k-2].iter().enumerate() {
+				next[i] -= a;
+			}
+			v.push(next);
+		}
+		v
+	}
+
+	pub fn degree(&self) -> usize {
+		self.psi.len() - 1
+	}
+
+// 2cos(2πk/n) = D_k(c), reduced
+	pub fn cos_x2(self: &Arc<Self>, k: i64) -> Cyclotomic {
+		let k = k.rem_euclid(self.n as i64) as usize;
+		let k = k.min(self.n - k);
+		let (mut d0, mut d1) = (Cyclotomic::from(2), self.generator());
+		if k == 0 {
+			return d0
+		}
+		for _ in 1..k {
+			let d2 = self.generator() * d1.clone() - d0;
+			d0 = d1;
+			d1 = d2;
+		}
+		d1
+	}
+
+	pub fn generator(self: &Arc<Self>) -> Cyclotomic {
+		Cyclotomic {
+			coef: vec![BigRational::zero(), BigRational::one()],
+			field: Some(self.clone()),
+		}.reduce()
+	}
+}
+
+// element of a real cyclotomic field, a polynomial with rational coefficients in its generator c reduced modulo ψ;
+// rational numbers need no field, so that zero and one can be constructed without one
+#[derive(Debug, Clone)]
+pub struct Cyclotomic {
+	coef: Vec<BigRational>,
+	field: Option<Arc<Field>>,
+}
+
+impl Cyclotomic {
+	pub fn rational(r: BigRational) -> Self {
+		Cyclotomic {
+			coef: vec![r],
+			field: None,
+		}.reduce()
+	}
+
+	pub fn from_decimal(x: &BigDecimal) -> Self {
+		let (n, scale) = x.as_bigint_and_exponent();
+		let ten = BigInt::from(10);
+		let r = if scale >= 0 {
+			BigRational::new(n, (0..scale).fold(BigInt::one(), |a, _| a * &ten))
+		} else {
+			BigRational::from_integer((0..-scale).fold(n, |a, _| a * &ten))
+		};
+		Cyclotomic::rational(r)
+	}
+
+// the value as a rational number, if it is one
+	pub fn to_rational(&self) -> Option<BigRational> {
+		match self.coef.len() {
+			0 => Some(BigRational::zero()),
+			1 => Some(self.coef[0].clone()),
+			_ => None,
+		}
+	}
+
+	pub fn to_f64(&self) -> f64 {
+		let to_f64 = |r: &BigRational| r.numer().to_string().parse::<f64>().unwrap() / r.denom().to_string().parse::<f64>().unwrap();
+		let c = self.field.as_ref().map_or(0.0, |f| 2.0 * (2.0 * std::f64::consts::PI / f.n as f64).cos());
+		self.coef.iter().rev().fold(0.0, |a, r| a * c + to_f64(r))
+	}
+
+	fn reduce(mut self) -> Self {
+		if let Some(field) = &self.field {
+			let d = field.degree();
+			while self.coef.len() > d {
+				let a = self.coef.pop().unwrap();
+				let top = self.coef.len() - d;
+				for (i, p) in field.psi[..d].iter().enumerate() {
+					self.coef[top + i] -= &a * BigRational::from_integer(p.clone());
+				}
+			}
+		}
+		while self.coef.last().is_some_and(Zero::is_zero) {
+			self.coef.pop();
+		}
+		self
+	}
+
+// enclosure of the value by exact rational interval arithmetic, c being enclosed to about w digits
+	fn enclosure(&self, w: usize) -> (BigRational, BigRational) {
+		let n = self.field.as_ref().expect("rational values need no enclosure").n;
+		let k = ((w as f64).sqrt() / 2.0).ceil() as u32;
+		let (lo, hi) = crate::cos_enclosure(2, n as u32, w, k);
+		let one = (0..w).fold(BigInt::one(), |a, _| a * 10);
+		let (c_lo, c_hi) = (BigRational::new(lo * 2, one.clone()), BigRational::new(hi * 2, one));
+		let mut acc = (BigRational::zero(), BigRational::zero());
+		for a in self.coef.iter().rev() {
+			let p = [&acc.0 * &c_lo, &acc.0 * &c_hi, &acc.1 * &c_lo, &acc.1 * &c_hi];
+			let min = p.iter().min().unwrap() + a;
+			let max = p.iter().max().unwrap() + a;
+			acc = (min, max);
+		}
+		acc
+	}
+
+// the sign, decided on ever tighter enclosures: this terminates, since an element is zero only if
+// all its coefficients are, ψ being irreducible
+	fn sign(&self) -> i8 {
+		if let Some(r) = self.to_rational() {
+			return if r.is_positive() {1} else if r.is_negative() {-1} else {0}
+		}
+		for w in (4..).map(|i| 1 << i) {
+			let (lo, hi) = self.enclosure(w);
+			if lo.is_positive() {
+				return 1
+			}
+			if hi.is_negative() {
+				return -1
+			}
+		}
+		unreachable!()
+	}
+
+// a·u = 1 modulo ψ by the extended Euclidean algorithm, r_i = s_i·a modulo ψ along the remainders,
+// which end with a nonzero constant since ψ is irreducible
+	pub fn inverse(&self) -> Self {
+		assert!(!self.is_zero(), "Division by zero");
+		let field = match (&self.field, self.to_rational()) {
+			(Some(field), None) => field.clone(),
+			(_, r) => return Self::rational(r.unwrap().recip()),
+		};
+		let mut r0 = field.psi.iter().map(|a| BigRational::from_integer(a.clone())).collect::<Vec<_>>();
+		let mut r1 = self.coef.clone();
+		let (mut s0, mut s1) = (Self::zero(), Self::one());
+		while r1.len() > 1 {
+			let (quotient, rem) = divide(r0, &r1);
+			let s2 = s0 - Cyclotomic {
+				coef: quotient,
+				field: Some(field.clone()),
+			}.reduce() * s1.clone();
+			r0 = r1;
+			r1 = rem;
+			s0 = s1;
+			s1 = s2;
+		}
+		s1 * Self::rational(r1[0].recip())
+	}
+
+	fn field(&self, other: &Self) -> Option<Arc<Field>> {
+		match (&self.field, &other.field) {
+			(Some(f), Some(g)) => {
+				assert!(f.n == g.n, "Elements of different cyclotomic fields");
+				Some(f.clone())
+			},
+			(f, g) => f.clone().or_else(|| g.clone()),
+		}
+	}
+
+// Appr::trq over the real cyclotomic field of c = 2cos(π/2q): the cosines 2cos(π(4mp-1)/2q) are exact,
+// and so are the coefficients, rational when Galois symmetry makes them so
+// (the transfer matrices are multiplied one by one: exact products of two large partial products cost more
+// than the q products with the small matrices, unlike in Appr::trq, where the partial products are rounded)
+	pub fn trq(p: usize, q: usize, lambda: &Self) -> Polynomial<Self> {
+		assert!(lambda.to_rational().is_some(), "λ must be rational");
+		let field = Arc::new(Field::new(4*q));
+		let half = lambda.clone() * Cyclotomic::rational(BigRational::new(1.into(), 2.into()));
+		let mut qq = Matrix::<Polynomial<Self>>::one();
+		for m in 1..=q {
+			let c = field.cos_x2((4*m*p) as i64 - 1) * half.clone();
+			let qm = Matrix::new((
+				(
+					Polynomial::from(vec![-c, Self::one()]),
+					-Polynomial::one()
+				),
+				(Polynomial::one(), Polynomial::zero())
+			));
+			qq = qm * qq;
+		}
+		qq.trace()
+	}
+
+// the number of bands of the square lattice, exactly: the 2q band edges are the roots of P(E) ∓ C, C = 2 + 2|λ/2|^q,
+// all real, and a double root is where two bands touch, so that D distinct roots make D - q bands
+	pub fn band_count(p: usize, q: usize, lambda: &Self) -> usize {
+		let c = Self::trace_bound(q, lambda);
+		let bound = Self::from(3) + lambda.abs();
+		let pol = Self::trq(p, q, lambda);
+		(pol.clone() - c.clone()).count_roots(&-bound.clone(), &bound) + (pol + c).count_roots(&-bound.clone(), &bound) - q
+	}
+
+// the discriminants of P(E) - C and P(E) + C: a gap closes, two band edges colliding, iff one of them vanishes
+	pub fn edge_discriminants(p: usize, q: usize, lambda: &Self) -> (Self, Self) {
+		let c = Self::trace_bound(q, lambda);
+		let pol = Self::trq(p, q, lambda);
+		((pol.clone() - c.clone()).discriminant(), (pol + c).discriminant())
+	}
+
+	fn trace_bound(q: usize, lambda: &Self) -> Self {
+		let half = lambda.abs() * Cyclotomic::rational(BigRational::new(1.into(), 2.into()));
+		Self::from(2) + (0..q).fold(Self::from(2), |a, _| a * half.clone())
+	}
+}
+
+// quotient and remainder of polynomials with rational coefficients, lowest first, without trailing zeros
+fn divide(mut a: Vec<BigRational>, b: &[BigRational]) -> (Vec<BigRational>, Vec<BigRational>) {
+	let mut quotient = vec![BigRational::zero(); (a.len() + 1).saturating_sub(b.len())];
+	while a.len() >= b.len() {
+		let f = a.last().unwrap() / b.last().unwrap();
+		let top = a.len() - b.len();
+		for (i, x) in b.iter().enumerate() {
+			a[top + i] -= &f * x;
+		}
+		quotient[top] = f;
+		while a.last().is_some_and(Zero::is_zero) {
+			a.pop();
+		}
+	}
+	(quotient, a)
+}
+
+impl From<i32> for Cyclotomic {
+	fn from(n: i32) -> Self {
+		Cyclotomic::rational(BigRational::from_integer(n.into()))
+	}
+}
+
+impl PartialEq for Cyclotomic {
+	fn eq(&self, other: &Self) -> bool {
+		self.coef == other.coef && (self.coef.len() <= 1 || self.field(other).is_some())
+	}
+}
+
+impl PartialOrd for Cyclotomic {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some((self.clone() - other.clone()).sign().cmp(&0))
+	}
+}
+
+impl fmt::Display for Cyclotomic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if let Some(r) = self.to_rational() {
+			return write!(f, "{}", r)
+		}
+		write!(f, "(")?;
+		let mut empty = true;
+		for (n, a) in self.coef.iter().enumerate().filter(|(_, a)| !a.is_zero()) {
+			if !empty && *a > BigRational::zero() {
+				write!(f, "+")?;
+			}
+			empty = false;
+			if n == 0 || !a.is_one() {
+				write!(f, "{}", a)?;
+			}
+			match n {
+				0 => (),
+				1 => write!(f, "c")?,
+				_ => write!(f, "c{}", n)?,
+			}
+		}
+		write!(f, ")")
+	}
+}
+
+impl ops::Add for Cyclotomic {
+	type Output = Self;
+	fn add(self, rhs: Self) -> Self {
+		let field = self.field(&rhs);
+		let (mut coef, other) = if self.coef.len() >= rhs.coef.len() {(self.coef, rhs.coef)} else {(rhs.coef, self.coef)};
+		for (a, b) in coef.iter_mut().zip(other) {
+			*a += b;
+		}
+		Cyclotomic {
+			coef,
+			field,
+		}.reduce()
+	}
+}
+
+impl ops::Neg for Cyclotomic {
+	type Output = Self;
+	fn neg(self) -> Self {
+		Cyclotomic {
+			coef: self.coef.into_iter().map(|a| -a).collect(),
+			field: self.field,
+		}
+	}
+}
+
+impl ops::Sub for Cyclotomic {
+	type Output = Self;
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn sub(self, rhs: Self) -> Self {
+		self + -rhs
+	}
+}
+
+impl ops::Mul for Cyclotomic {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self {
+		let field = self.field(&rhs);
+		if self.coef.is_empty() || rhs.coef.is_empty() {
+			return Self::zero()
+		}
+		let mut coef = vec![BigRational::zero(); self.coef.len() + rhs.coef.len() - 1];
+		for (i, a) in self.coef.iter().enumerate() {
+			for (j, b) in rhs.coef.iter().enumerate() {
+				coef[i + j] += a * b;
+			}
+		}
+		Cyclotomic {
+			coef,
+			field,
+		}.reduce()
+	}
+}
+
+impl ops::Div for Cyclotomic {
+	type Output = Self;
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn div(self, rhs: Self) -> Self {
+		self * rhs.inverse()
+	}
+}
+
+// division is exact in a field
+impl ops::Rem for Cyclotomic {
+	type Output = Self;
+	fn rem(self, rhs: Self) -> Self {
+		assert!(!rhs.is_zero(), "Division by zero");
+		Self::zero()
+	}
+}
+
+impl Zero for Cyclotomic {
+	fn zero() -> Self {
+		Cyclotomic {
+			coef: Vec::new(),
+			field: None,
+		}
+	}
+
+	fn is_zero(&self) -> bool {
+		self.coef.is_empty()
+	}
+}
+
+impl One for Cyclotomic {
+	fn one() -> Self {
+		1.into()
+	}
+}
+
+impl Num for Cyclotomic {
+	type FromStrRadixErr = <BigRational as Num>::FromStrRadixErr;
+	fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+		BigRational::from_str_radix(s, radix).map(Self::rational)
+	}
+}
+
+impl Signed for Cyclotomic {
+	fn abs(&self) -> Self {
+		if self.sign() < 0 {-self.clone()} else {self.clone()}
+	}
+
+	fn abs_sub(&self, other: &Self) -> Self {
+		if self <= other {Self::zero()} else {self.clone() - other.clone()}
+	}
+
+	fn signum(&self) -> Self {
+		(self.sign() as i32).into()
+	}
+
+	fn is_positive(&self) -> bool {
+		self.sign() > 0
+	}
+
+	fn is_negative(&self) -> bool {
+		self.sign() < 0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Appr;
+
+	#[test]
+	fn field() {
+		// 2cos(2π/5) = (√5 - 1)/2 solves c^2 + c - 1 = 0, 2cos(π/4) = √2 solves c^2 - 2 = 0
+		assert_eq!(Field::new(5).psi, vec![(-1).into(), 1.into(), 1.into()]);
+		assert_eq!(Field::new(8).psi, vec![(-2).into(), 0.into(), 1.into()]);
+		assert_eq!(Field::new(12).degree(), 2);
+		let f = Arc::new(Field::new(12));
+		assert_eq!(f.cos_x2(2), 1.into());
+		assert_eq!(f.cos_x2(3), 0.into());
+		assert_eq!(f.cos_x2(-6), (-2).into());
+		assert_eq!(f.cos_x2(1) * f.cos_x2(1), 3.into());
+		for k in 0..24 {
+			assert!((f.cos_x2(k).to_f64() - f64::cos_rational_x2(k, 6, 0)).abs() < 1e-12);
+		}
+	}
+
+	#[test]
+	fn exact_trq() {
+		for &(p, q, lambda) in &[(1, 3, 2), (2, 5, 2), (3, 7, 3), (3, 8, 2), (5, 12, 1)] {
+			let exact = Cyclotomic::trq(p, q, &lambda.into()).into_vec();
+			let appr = f64::trq(p, q, &(lambda as f64), 0).into_vec();
+			assert_eq!(exact.len(), appr.len());
+			assert!(exact.iter().zip(&appr).all(|(a, b)| (a.to_f64() - b).abs() < 1e-8 * (1.0 + b.abs())));
+		}
+		// the square lattice at flux 1/3: E^3 - 6E, up to the constant
+		let t = Cyclotomic::trq(1, 3, &2.into());
+		assert_eq!(t.into_vec().into_iter().map(|a| a.to_string()).collect::<Vec<_>>(), vec!["0", "-6", "0", "1"]);
+		let half = Cyclotomic::from_decimal(&"0.5".parse().unwrap());
+		assert_eq!(half.to_rational(), Some(BigRational::new(1.into(), 2.into())));
+		let t = Cyclotomic::trq(2, 5, &Cyclotomic::from_decimal(&"1.3".parse().unwrap())).into_vec();
+		assert!(t.iter().zip(f64::trq(2, 5, &1.3, 0).as_ref()).all(|(a, b)| (a.to_f64() - b).abs() < 1e-10));
+	}
+
+	#[test]
+	fn ordered_field() {
+		let f = Arc::new(Field::new(20));
+		let c = f.generator();
+		let x = c.clone() * c.clone() - Cyclotomic::from(3) + c.clone();
+		assert_eq!(x.clone() * x.inverse(), 1.into());
+		assert_eq!(Cyclotomic::from(3) / Cyclotomic::from(4), Cyclotomic::rational(BigRational::new(3.into(), 4.into())));
+		for k in 0..20 {
+			let y = f.cos_x2(k) - f.cos_x2(3);
+			let v = y.to_f64();
+			assert_eq!(y.is_positive(), v > 1e-12);
+			assert_eq!(y.is_negative(), v < -1e-12);
+		}
+		// 2cos(π/10) and 2cos(3π/10) are roots of c^4 - 5c^2 + 5, the other two are negative
+		let pol = Polynomial::from(vec![5.into(), 0.into(), (-5).into(), 0.into(), Cyclotomic::one()]);
+		assert_eq!(pol.count_roots(&0.into(), &2.into()), 2);
+		assert_eq!(pol.count_roots(&f.cos_x2(2), &2.into()), 1);
+		// (E - 1)^2 (E + 2): multiple roots count once
+		let pol = Polynomial::from(vec![2.into(), (-3).into(), 0.into(), Cyclotomic::one()]);
+		assert_eq!(pol.count_roots(&(-3).into(), &3.into()), 2);
+	}
+
+	#[test]
+	fn band_count() {
+		for q in 2..=7 {
+			for p in (1..=q/2).filter(|p| (1..=*p).all(|d| d == 1 || p % d != 0 || q % d != 0)) {
+				let n = Cyclotomic::band_count(p, q, &2.into());
+				assert!(n == q || n == q - 1 && q % 2 == 0, "{}/{}: {}", p, q, n);
+				// the intervals that touch are listed separately
+				let int = f64::intervals(p, q, crate::Lattice::Square, &2.0, 0);
+				assert_eq!(n, 1 + int.windows(2).filter(|w| w[1].0 > w[0].1).count());
+			}
+		}
+		assert_eq!(Cyclotomic::band_count(1, 4, &1.into()), 3);
+		assert_eq!(Cyclotomic::band_count(2, 5, &Cyclotomic::from_decimal(&"0.5".parse().unwrap())), 5);
+		assert_eq!(Cyclotomic::band_count(2, 5, &Cyclotomic::from_decimal(&"-0.5".parse().unwrap())), 5);
+		assert_eq!(Cyclotomic::band_count(1, 3, &(-3).into()), 3);
+	}
+
+	#[test]
+	fn localize_edges() {
+		// the band edges of 2/5, the roots of P(E) ∓ C, isolated exactly by disjoint brackets
+		let (p, q) = (2, 5);
+		let pol = Cyclotomic::trq(p, q, &2.into());
+		let c = Cyclotomic::trace_bound(q, &2.into());
+		let eps = Cyclotomic::rational(BigRational::new(1.into(), 1000.into()));
+		let edges = f64::intervals(p, q, crate::Lattice::Square, &2.0, 0).into_iter().flat_map(|(l, r)| [l, r]).collect::<Vec<_>>();
+		let mut found = 0;
+		for pol in [pol.clone() - c.clone(), pol + c] {
+			let brackets = pol.localize_roots((-5).into(), 5.into(), &eps);
+			assert!(brackets.windows(2).all(|w| w[0].1 <= w[1].0));
+			for (l, r) in brackets {
+				let inside = edges.iter().filter(|&&x| l.to_f64() - 1e-9 <= x && x <= r.to_f64() + 1e-9).count();
+				assert!(inside >= 1);
+				found += 1;
+			}
+		}
+		assert_eq!(found, 2 * q);
+	}
+	
+	#[test]
+	fn edge_discriminants() {
+		// the two central bands touch for even q
+		let (a, b) = Cyclotomic::edge_discriminants(1, 4, &2.into());
+		assert!(a.is_zero() != b.is_zero());
+		let (a, b) = Cyclotomic::edge_discriminants(1, 4, &1.into());
+		assert!(a.is_zero() != b.is_zero());
+		let (a, b) = Cyclotomic::edge_discriminants(2, 5, &2.into());
+		assert!(!a.is_zero() && !b.is_zero());
+		// the touching bands make E = 0 a double root, split off exactly along the subresultant sequence
+		let pol = Cyclotomic::trq(1, 4, &2.into());
+		let c = Cyclotomic::trace_bound(4, &2.into());
+		let multiplicities = [pol.clone() - c.clone(), pol + c].iter().map(|p| {
+			let f = p.square_free(&Cyclotomic::zero()).unwrap();
+			assert_eq!(f.iter().map(|(f, m)| f.degree() * m).sum::<usize>(), 4);
+			f.iter().map(|(_, m)| *m).max().unwrap()
+		}).collect::<Vec<_>>();
+		assert!(multiplicities == [2, 1] || multiplicities == [1, 2]);
+	}
+}
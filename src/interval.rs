@@ -0,0 +1,432 @@
+use crate::{Zero, One, Signed, Num, Decimal, Appr, Lattice};
+use crate::polynomial::{Polynomial, Certified};
+use std::{ops, fmt, cmp::Ordering, str::FromStr};
+
+// closed interval [lo, hi] enclosing an exact real number: the bounds of every result are rounded outward,
+// so the exact result of an operation on the enclosed numbers is enclosed too
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+	lo: f64,
+	hi: f64,
+}
+
+const ENTIRE: Interval = Interval {
+	lo: f64::NEG_INFINITY,
+	hi: f64::INFINITY,
+};
+
+// bounds of the exact value x + e, x being the value rounded to nearest and e its (exactly known) rounding error;
+// without a usable error term, e.g. on overflow or underflow, x is widened by an ulp either way
+fn directed(x: f64, e: f64) -> (f64, f64) {
+	if x.is_nan() || e.is_nan() {
+		(f64::NEG_INFINITY, f64::INFINITY)
+	} else if !x.is_finite() || !e.is_finite() || (x != 0.0 && x.abs() < 1e-290) {
+		(x.next_down(), x.next_up())
+	} else {
+		(if e < 0.0 {x.next_down()} else {x}, if e > 0.0 {x.next_up()} else {x})
+	}
+}
+
+// Knuth's TwoSum
+fn sum(a: f64, b: f64) -> (f64, f64) {
+	let s = a + b;
+	let bb = s - a;
+	directed(s, (a - (s - bb)) + (b - bb))
+}
+
+fn product(a: f64, b: f64) -> (f64, f64) {
+	if a == 0.0 || b == 0.0 {
+		return if (a * b).is_nan() {(f64::NEG_INFINITY, f64::INFINITY)} else {(0.0, 0.0)}
+	}
+	let p = a * b;
+	directed(p, a.mul_add(b, -p))
+}
+
+fn quotient(a: f64, b: f64) -> (f64, f64) {
+	let q = a / b;
+	directed(q, -q.mul_add(b, -a) * b.signum())
+}
+
+impl Interval {
+	pub fn new(lo: f64, hi: f64) -> Self {
+		assert!(lo <= hi, "Empty interval [{}, {}]", lo, hi);
+		Interval {
+			lo,
+			hi,
+		}
+	}
+
+	pub fn point(x: f64) -> Self {
+		Interval::new(x, x)
+	}
+
+	pub fn lo(&self) -> f64 {
+		self.lo
+	}
+
+	pub fn hi(&self) -> f64 {
+		self.hi
+	}
+
+	pub fn width(&self) -> f64 {
+		self.hi - self.lo
+	}
+
+	pub fn contains(&self, x: f64) -> bool {
+		self.lo <= x && x <= self.hi
+	}
+
+	pub fn hull(&self, other: &Self) -> Self {
+		Interval::new(self.lo.min(other.lo), self.hi.max(other.hi))
+	}
+
+	fn from_bounds(candidates: [(f64, f64); 4]) -> Self {
+		Interval::new(
+			candidates.iter().map(|c| c.0).fold(f64::INFINITY, f64::min),
+			candidates.iter().map(|c| c.1).fold(f64::NEG_INFINITY, f64::max),
+		)
+	}
+
+// band edges of the square lattice as in intervals_square, each enclosed by certified Sturm counts;
+// the error is the range of energies where the signs of the Sturm sequence could not be decided
+// (for large q, or for edges closer than eps)
+	pub fn certified_intervals(p: usize, q: usize, lambda: &Self) -> Result<Vec<(Self, Self)>, Self> {
+		let bound = Self::spectral_bound(lambda);
+		if q == 1 {
+			return Ok(vec![(-bound, bound)])
+		}
+		let pol = Self::trq(p, q, lambda, 0);
+		let eps = Self::eps(0);
+		let c = Self::trace_bound(q, lambda, 0);
+		let roots = |pol: Polynomial<Self>, left: Self, right: Self| pol.certified_roots(left, right, &eps)
+			.map(|r| r.into_iter().map(|(l, r)| l.hull(&r)).collect::<Vec<_>>())
+			.map_err(|(l, r)| l.hull(&r));
+		let r = if q.is_multiple_of(2) {
+			let mut v = pol.into_vec();
+			v[0] = if q.is_multiple_of(4) {c + c} else {-(c + c)};
+			for i in 1..=q/2 {
+				v.swap(i, 2*i);
+			}
+			v.truncate(q/2+1);
+			let bound2 = bound * bound;
+			let mut r1 = roots(Polynomial::from(v.clone()), Self::zero(), bound2).map_err(Self::sqrt)?;
+			v.remove(0);
+			let mut r2 = roots(Polynomial::from(v), Self::zero(), bound2).map_err(Self::sqrt)?;
+			let mut r = vec![Self::zero()];
+			r.append(&mut r1);
+			r.append(&mut r2);
+			r.into_iter().map(Self::sqrt).collect()
+		} else {
+			let mut v = pol.into_vec();
+			v[0] = c;
+			for i in 1..=q/2 {
+				v[2*i] = Self::zero();
+			}
+			roots(Polynomial::from(v), -bound, bound)?
+		};
+		let mut r = Self::mirror_edges(Lattice::Square, r);
+		r.sort_by(|a, b| a.lo.total_cmp(&b.lo));
+// the order of the edges is certain only if their enclosures are disjoint
+		if let Some(w) = r.windows(2).find(|w| w[0].hi >= w[1].lo && !(w[0] == w[1] && w[0].width() == 0.0)) {
+			return Err(w[0].hull(&w[1]))
+		}
+		let mut r2 = Vec::new();
+		let mut ri = r.into_iter();
+		while let (Some(x1), Some(x2)) = (ri.next(), ri.next()) {
+			r2.push((x1, x2));
+		}
+		Ok(r2)
+	}
+}
+
+impl Decimal for Interval {}
+
+impl Certified for Interval {
+	fn certain_sign(&self) -> Option<i8> {
+		if self.lo == 0.0 && self.hi == 0.0 {
+			Some(0)
+		} else if self.lo > 0.0 {
+			Some(1)
+		} else if self.hi < 0.0 {
+			Some(-1)
+		} else {
+			None
+		}
+	}
+
+	fn between(a: &Self, b: &Self, k: i32, n: i32) -> Self {
+		let (x, y) = (a.hi, b.lo);
+		Interval::point(x + (y - x) * k as f64 / n as f64)
+	}
+}
+
+impl fmt::Display for Interval {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "[{}, {}]", self.lo, self.hi)
+	}
+}
+
+// integers are exact, other decimal numbers are enclosed by the neighbours of the nearest f64
+impl FromStr for Interval {
+	type Err = <f64 as FromStr>::Err;
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Ok(n) = s.parse::<i32>() {
+			return Ok(n.into())
+		}
+		let x = s.parse::<f64>()?;
+		Ok(Interval::new(x.next_down(), x.next_up()))
+	}
+}
+
+impl From<i32> for Interval {
+	fn from(n: i32) -> Self {
+		Interval::point(n as f64)
+	}
+}
+
+impl PartialOrd for Interval {
+// ordered when one of the intervals lies entirely below the other, equal only when they coincide
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		if self == other {
+			Some(Ordering::Equal)
+		} else if self.hi < other.lo {
+			Some(Ordering::Less)
+		} else if self.lo > other.hi {
+			Some(Ordering::Greater)
+		} else {
+			None
+		}
+	}
+}
+
+impl ops::Neg for Interval {
+	type Output = Self;
+	fn neg(self) -> Self {
+		// + 0.0 turns -0 into 0
+		Interval::new(-self.hi + 0.0, -self.lo + 0.0)
+	}
+}
+
+impl ops::Add for Interval {
+	type Output = Self;
+	fn add(self, rhs: Self) -> Self {
+		Interval::new(sum(self.lo, rhs.lo).0, sum(self.hi, rhs.hi).1)
+	}
+}
+
+impl ops::Sub for Interval {
+	type Output = Self;
+	#[allow(clippy::suspicious_arithmetic_impl)]
+	fn sub(self, rhs: Self) -> Self {
+		self + -rhs
+	}
+}
+
+impl ops::Mul for Interval {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self {
+		Interval::from_bounds([
+			product(self.lo, rhs.lo),
+			product(self.lo, rhs.hi),
+			product(self.hi, rhs.lo),
+			product(self.hi, rhs.hi),
+		])
+	}
+}
+
+impl ops::Div for Interval {
+	type Output = Self;
+	fn div(self, rhs: Self) -> Self {
+		if rhs.contains(0.0) {
+			return ENTIRE
+		}
+		Interval::from_bounds([
+			quotient(self.lo, rhs.lo),
+			quotient(self.lo, rhs.hi),
+			quotient(self.hi, rhs.lo),
+			quotient(self.hi, rhs.hi),
+		])
+	}
+}
+
+// a - nb with n the quotient truncated towards zero, if it is certain
+impl ops::Rem for Interval {
+	type Output = Self;
+	fn rem(self, rhs: Self) -> Self {
+		let n = self / rhs;
+		if n.lo.trunc() != n.hi.trunc() {
+			return ENTIRE
+		}
+		self - rhs * Interval::point(n.lo.trunc())
+	}
+}
+
+impl Zero for Interval {
+	fn zero() -> Self {
+		Interval::point(0.0)
+	}
+
+	fn is_zero(&self) -> bool {
+		self.lo == 0.0 && self.hi == 0.0
+	}
+}
+
+impl One for Interval {
+	fn one() -> Self {
+		Interval::point(1.0)
+	}
+}
+
+impl Num for Interval {
+	type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+	fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+		let x = f64::from_str_radix(s, radix)?;
+		Ok(Interval::new(x.next_down(), x.next_up()))
+	}
+}
+
+impl Signed for Interval {
+	fn abs(&self) -> Self {
+		if self.lo >= 0.0 {
+			*self
+		} else if self.hi <= 0.0 {
+			-*self
+		} else {
+			Interval::new(0.0, self.hi.max(-self.lo))
+		}
+	}
+
+	fn abs_sub(&self, other: &Self) -> Self {
+		let d = *self - *other;
+		if d.hi <= 0.0 {Self::zero()} else {Interval::new(d.lo.max(0.0), d.hi)}
+	}
+
+	fn signum(&self) -> Self {
+		match self.certain_sign() {
+			Some(s) => (s as i32).into(),
+			None => Interval::new(if self.lo < 0.0 {-1.0} else {0.0}, if self.hi > 0.0 {1.0} else {0.0}),
+		}
+	}
+
+	fn is_positive(&self) -> bool {
+		self.lo > 0.0
+	}
+
+	fn is_negative(&self) -> bool {
+		self.hi < 0.0
+	}
+}
+
+impl Appr for Interval {
+	fn accu(self, _p: i64) -> Self {
+		self
+	}
+
+// the subtractions of Karatsuba's method would widen the intervals
+	const KARATSUBA: bool = false;
+
+	fn eps(_q: i64) -> Self {
+		Interval::point(1e-14)
+	}
+
+// the square root of the nonnegative part
+	fn sqrt(self) -> Self {
+		if self.hi < 0.0 {
+			return ENTIRE
+		}
+		let root = |x: f64| {
+			let s = x.max(0.0).sqrt();
+			directed(s, -s.mul_add(s, -x.max(0.0)))
+		};
+		Interval::new(root(self.lo).0, root(self.hi).1)
+	}
+
+// the enclosure has no further error
+	fn cos_rational_x2_err(p: i64, q: i64, accu: i64) -> (Self, Self) {
+		(Self::cos_rational_x2(p, q, accu), Self::zero())
+	}
+
+// exact at the rational values of the cosine, otherwise the f64 cosine widened by the rounding errors
+// of π, of the argument and of the cosine itself (faithful rounding of libm is assumed)
+	fn cos_rational_x2(p: i64, q: i64, _accu: i64) -> Self {
+		let (p, q, sign) = Self::reduce_args(p, q);
+		let c = if p == 0 {
+			2.into()
+		} else if 2 * p == q {
+			Self::zero()
+		} else if 3 * p == q {
+			Self::one()
+		} else {
+			let c = 2.0 * (std::f64::consts::PI * p as f64 / q as f64).cos();
+			Interval::new((c - 4e-15).next_down(), (c + 4e-15).next_up())
+		};
+		if sign == 1 {c} else {-c}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bigdecimal::BigDecimal;
+
+	// compared exactly: the decimal expansion of an f64 is finite
+	fn encloses(i: &Interval, x: &BigDecimal) -> bool {
+		let exact = |y: f64| format!("{:.80e}", y).parse::<BigDecimal>().unwrap();
+		exact(i.lo) <= *x && *x <= exact(i.hi)
+	}
+
+	#[test]
+	fn arithmetic() {
+		let third = Interval::one() / Interval::from(3);
+		assert!(third.width() > 0.0 && third.contains(1.0 / 3.0));
+		assert!((third * 3.into()).contains(1.0));
+		assert!((third - third).contains(0.0));
+		assert_eq!(Interval::from(2) * 3.into() - 1.into(), 5.into());
+		let tenth: Interval = "0.1".parse().unwrap();
+		assert!((tenth * 10.into()).contains(1.0) && tenth.is_positive());
+		assert!(encloses(&tenth, &"0.1".parse().unwrap()));
+		assert!(Interval::from(2).sqrt().contains(2f64.sqrt()));
+		assert!((Interval::from(2).sqrt() * Interval::from(2).sqrt()).contains(2.0));
+		assert_eq!(Interval::from(9).sqrt(), 3.into());
+		assert_eq!(Interval::one() / Interval::new(-1.0, 1.0), ENTIRE);
+		assert!(!Interval::new(-1.0, 1.0).is_positive() && !Interval::new(-1.0, 1.0).is_negative());
+		assert_eq!(Interval::new(-3.0, 1.0).abs(), Interval::new(0.0, 3.0));
+		assert_eq!(Interval::new(-3.0, 1.0).partial_cmp(&Interval::new(0.5, 2.0)), None);
+		assert!(Interval::new(-3.0, 1.0) < Interval::new(1.5, 2.0));
+		for &(p, q) in &[(1, 5), (2, 7), (5, 12), (7, 9), (1, 1000)] {
+			let c = Interval::cos_rational_x2(p, q, 0);
+			assert!(encloses(&c, &BigDecimal::cos_rational_x2(p, q, 30)) && c.width() < 1e-13);
+		}
+		assert_eq!(Interval::cos_rational_x2(1, 3, 0), Interval::one());
+		assert_eq!(Interval::cos_rational_x2(2, 3, 0), -Interval::one());
+	}
+
+	#[test]
+	fn certified_roots() {
+		// (x - 1)(x - 2)(x + 1/3)
+		let third = Interval::one() / Interval::from(3);
+		let pol = Polynomial::<Interval>::from(vec![2.into(), (-3).into(), 1.into()]) * Polynomial::from(vec![third, 1.into()]);
+		let r = pol.certified_roots((-5).into(), 5.into(), &Interval::eps(0)).unwrap();
+		assert_eq!(r.len(), 3);
+		for ((l, r), x) in r.into_iter().zip(&[-1.0 / 3.0, 1.0, 2.0]) {
+			assert!(l.hull(&r).contains(*x) && l.hull(&r).width() < 1e-13);
+		}
+		// a double root cannot be separated from a pair of close roots
+		let pol = Polynomial::from(vec![Interval::new(1.0, 1.0 + 1e-12), (-2).into(), 1.into()]);
+		assert!(pol.certified_roots((-5).into(), 5.into(), &Interval::eps(0)).is_err());
+	}
+
+	#[test]
+	fn certified_intervals() {
+		for &(p, q, lambda) in &[(1, 2, "2"), (1, 3, "2"), (1, 4, "2"), (2, 5, "2"), (3, 7, "1.5"), (1, 3, "1.3")] {
+			let exact = BigDecimal::intervals_matrix(p, q, &lambda.parse().unwrap(), 16);
+			let int = Interval::certified_intervals(p, q, &lambda.parse().unwrap()).unwrap();
+			assert_eq!(exact.len(), int.len());
+			for ((l, r), (li, ri)) in exact.into_iter().zip(int) {
+				assert!(encloses(&li, &l) && encloses(&ri, &r), "{}/{}: {} {} {} {}", p, q, l, r, li, ri);
+				assert!(li.width() < 1e-10 && ri.width() < 1e-10);
+			}
+		}
+		assert!(Interval::certified_intervals(1, 25, &2.into()).is_err());
+	}
+}
@@ -1,65 +1,275 @@
 use bigdecimal::{Zero, One, Signed, Num, BigDecimal};
+use num_bigint::BigInt;
 
 mod polynomial;
-use polynomial::Polynomial;
+pub use polynomial::{Polynomial, Isolation};
 
 mod matrix2x2;
 use matrix2x2::Matrix;
 
+mod matrix;
+
+mod tridiagonal;
+
+mod interval;
+pub use interval::Interval;
+
+mod ball;
+use ball::Ball;
+
+mod cyclotomic;
+pub use cyclotomic::Cyclotomic;
+
+mod aberth;
+pub use num_complex::Complex;
+
+mod cache;
+pub use cache::ResultCache;
+
+// number of phases sampled per quarter period when no Chambers-type relation is available
+const NNN_PHASES: i64 = 8;
+
 pub trait Decimal: Clone + PartialOrd + Num + Signed + From<i32> {}
 impl Decimal for f64 {}
 impl Decimal for BigDecimal {}
 
+// hoppings beyond nearest neighbours: t' along both diagonals and t2 to second neighbours along x
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hopping<T> {
+	pub diagonal: T,
+	pub second: T,
+}
+
+impl<T: Zero> Hopping<T> {
+	pub fn new(diagonal: T, second: T) -> Self {
+		Hopping {
+			diagonal,
+			second,
+		}
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.diagonal.is_zero() && self.second.is_zero()
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lattice {
+	Square,
+	Honeycomb,
+	Triangular,
+}
+
+impl Lattice {
+// the spectrum as a function of the flux is periodic with this period (in flux quanta per unit cell)
+// and symmetric under p/q -> period - p/q
+	pub fn flux_period(self) -> usize {
+		match self {
+			Lattice::Triangular => 2,
+			_ => 1,
+		}
+	}
+
+// whether the spectrum is symmetric under E -> -E
+	pub fn is_symmetric(self) -> bool {
+		self != Lattice::Triangular
+	}
+
+// number of bands at flux p/q, two sites per unit cell on the honeycomb lattice
+	pub fn bands(self, q: usize) -> usize {
+		match self {
+			Lattice::Honeycomb => 2 * q,
+			_ => q,
+		}
+	}
+}
+
+// Hall conductance σ (and s) of the gap above r bands at flux p/q,
+// from the TKNN Diophantine equation r = qs + pσ with |σ| <= q/2 (σ = q/2 rather than -q/2 for even q)
+pub fn tknn(p: usize, q: usize, r: usize) -> (i64, i64) {
+	let (p, q, r) = (p as i64, q as i64, r as i64);
+	let sigma = (-(q - 1) / 2..=q / 2).find(|sigma| (r - p * sigma) % q == 0).expect("p and q must be coprime");
+	(sigma, (r - p * sigma) / q)
+}
+
+impl std::str::FromStr for Lattice {
+	type Err = String;
+	fn from_str(s: &str) -> Result<Self, String> {
+		match s {
+			"square" => Ok(Lattice::Square),
+			"honeycomb" => Ok(Lattice::Honeycomb),
+			"triangular" => Ok(Lattice::Triangular),
+			_ => Err(format!("Unknown lattice '{}'", s)),
+		}
+	}
+}
+
 pub trait Appr: Decimal {
-	fn trq(p: usize, q: usize, accu: i64) -> Polynomial<Self> {
-		let mut qq = Matrix::<Polynomial<Self>>::one();
-		for m in 1..=q {
-			let qm = Matrix::new((
+// Aubry–André chain ψ(m+1) + ψ(m-1) + λcos(θ_m)ψ(m) = Eψ(m), λ = 2t_y/t_x (2 for the square lattice)
+	fn trq(p: usize, q: usize, lambda: &Self, accu: i64) -> Polynomial<Self> {
+		Self::trq_with_error(p, q, lambda, accu).0
+	}
+
+// trq with a bound on the error of every coefficient: the error bounds of the cosines (cos_rational_x2_err)
+// and of every rounding are propagated through the product of the transfer matrices
+	fn trq_with_error(p: usize, q: usize, lambda: &Self, accu: i64) -> (Polynomial<Self>, Vec<Self>) {
+		let half = Ball::exact(lambda.clone() / Self::from(2));
+		let qm = (1..=q).map(|m| {
+			let (c, err) = Self::cos_rational_x2_err((4*m*p-1) as i64, (2*q) as i64, accu);
+			let c = Ball::new(c, err);
+			let c = if half.mid.is_one() {c} else {(c * half.clone()).accu(accu)};
+			Matrix::new((
 				(
-					Polynomial::from(vec![-Self::cos_rational_x2((4*m*p-1) as i64, (2*q) as i64, accu), Self::one()]),
+					Polynomial::from(vec![-c, Ball::one()]),
 					-Polynomial::one()
 				),
 				(Polynomial::one(), Polynomial::zero())
-			));
-			qq = qm * qq;
+			))
+		}).collect::<Vec<_>>();
+// the products of transfer_product on the midpoints
+		let digits = Self::product_digits(q, &Self::spectral_bound(lambda), accu);
+		let qq = product_tree(&qm, Self::KARATSUBA, |x: Ball<Self>| x.accu(digits));
+		let (p, err): (Vec<_>, Vec<_>) = qq.trace().into_vec().into_iter()
+			.map(|x| if x.mid.is_one() || x.mid.is_zero() {x} else {x.accu(accu)})
+			.map(|x| (x.mid, x.rad))
+			.unzip();
+		(p.into(), err)
+	}
+
+// m_q ⋯ m_1 along a balanced tree, from transfer matrices whose rows have coefficients of absolute sum at most growth:
+// the partial products are rounded to accu digits and those of q^2·growth^q, which bounds how much their rounding
+// errors are amplified, so the result is accurate to about eps(accu) while the digits stay few
+	fn transfer_product(qm: &[Matrix<Polynomial<Self>>], growth: &Self, accu: i64) -> Matrix<Polynomial<Self>> {
+		let digits = Self::product_digits(qm.len(), growth, accu);
+		product_tree(qm, Self::KARATSUBA, |x: Self| x.accu(digits))
+	}
+
+	fn product_digits(q: usize, growth: &Self, accu: i64) -> i64 {
+		let ten = Self::from(10);
+		let mut digits = accu + 3;
+		let mut x = Self::from((q * q) as i32);
+		for _ in 0..=q {
+			while x >= ten {
+				x = x / ten.clone();
+				digits += 1;
+			}
+			x = (x * growth.clone()).accu(3);
+		}
+		digits
+	}
+
+	fn spectral_bound(lambda: &Self) -> Self {
+		Self::from(2) + lambda.abs()
+	}
+
+// (λ/2)^q, the amplitude of the phase-dependent part of the trace, see Chambers' relation
+	fn chambers_amplitude(q: usize, lambda: &Self, accu: i64) -> Self {
+		let half = lambda.clone() / Self::from(2);
+		let mut mu = Self::one();
+		for _ in 0..q {
+			mu = (mu * half.clone()).accu(accu);
+		}
+		mu
+	}
+
+// band edges are where the trace reaches ±(2 + 2|λ/2|^q), whatever the sign of λ
+	fn trace_bound(q: usize, lambda: &Self, accu: i64) -> Self {
+		let mu = Self::chambers_amplitude(q, lambda, accu).abs();
+		Self::from(2) + mu.clone() + mu
+	}
+
+// band edges without the characteristic polynomial: by Chambers' relation they are the eigenvalues
+// of the q×q Harper matrix at kx = ky = 0 (periodic) and at kx = ky = π/q (antiperiodic),
+// found by bisection on the Sturm counts of the matrix itself
+	fn intervals_matrix(p: usize, q: usize, lambda: &Self, accu: i64) -> Vec<(Self, Self)> {
+		let half = lambda.abs() / Self::from(2);
+		let bound = Self::spectral_bound(lambda) + Self::one();
+		let (p, qi) = (p as i64, q as i64);
+		let mut r = Vec::new();
+		for (shift, corner) in [(0, Self::one()), (1, -Self::one())] {
+			let diag = (0..qi).map(|m| (Self::cos_rational_x2(2*p*m + shift, qi, accu + 2) * half.clone()).accu(accu + 2)).collect();
+			r.append(&mut tridiagonal::Cyclic::new(diag, corner).eigenvalues(&bound, accu));
+		}
+		r.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let mut r2 = Vec::new();
+		let mut ri = r.into_iter();
+		while let (Some(x1), Some(x2)) = (ri.next(), ri.next()) {
+			r2.push((x1, x2));
+		}
+		r2
+	}
+
+// Chambers' relation: the trace at phase ν is P(E) - 2(λ/2)^q cos(qν), P being the trace at the midband phase (trq),
+// so the q energies at Bloch momenta kx (along the chain) and ky (= ν) solve
+// P(E) - 2cos(q kx) - 2(λ/2)^q cos(q ky) = 0; the momenta are rational multiples of π, k = πk.0/k.1
+	fn chambers(p: usize, q: usize, lambda: &Self, kx: (i64, i64), ky: (i64, i64), accu: i64) -> Polynomial<Self> {
+		let mu = Self::chambers_amplitude(q, lambda, accu);
+		let qi = q as i64;
+		let c = Self::cos_rational_x2(qi * kx.0, kx.1, accu) + (Self::cos_rational_x2(qi * ky.0, ky.1, accu) * mu).accu(accu);
+		Self::trq(p, q, lambda, accu) - c
+	}
+
+	fn bloch_energies(p: usize, q: usize, lambda: &Self, kx: (i64, i64), ky: (i64, i64), accu: i64) -> Vec<Self> {
+		let bound = Self::spectral_bound(lambda) + Self::one();
+		Self::chambers(p, q, lambda, kx, ky, accu).find_roots(-bound.clone(), bound, &Self::eps(accu), &Self::gcd_tolerance(accu)).into_iter().map(|x| x.accu(accu)).collect()
+	}
+
+	fn intervals(p: usize, q: usize, lattice: Lattice, lambda: &Self, accu: i64) -> Vec<(Self, Self)> {
+		Self::intervals_by(Isolation::Sturm, p, q, lattice, lambda, accu)
+	}
+
+// with the real roots of the polynomials isolated by the given method
+	fn intervals_by(isolation: Isolation, p: usize, q: usize, lattice: Lattice, lambda: &Self, accu: i64) -> Vec<(Self, Self)> {
+		match lattice {
+			Lattice::Square => Self::intervals_square(isolation, p, q, lambda, accu),
+			Lattice::Honeycomb => Self::intervals_honeycomb(isolation, p, q, lambda, accu),
+			Lattice::Triangular => Self::intervals_triangular(isolation, p, q, lambda, accu),
 		}
-		let p = qq.trace();
-		p.into_vec().into_iter().map(|x| if x.is_one() || x.is_zero() {x} else {x.accu(accu)}).collect::<Vec<_>>().into()
 	}
 
-	fn intervals(p: usize, q: usize, accu: i64) -> Vec<(Self, Self)> {
+// the band edges together with their mirror images under E -> -E, which only the symmetric lattices allow
+	fn mirror_edges(lattice: Lattice, mut r: Vec<Self>) -> Vec<Self> {
+		assert!(lattice.is_symmetric(), "the spectrum on the {:?} lattice is not symmetric under E -> -E", lattice);
+		let mut nr = r.iter().map(|x| -x.clone()).collect();
+		r.append(&mut nr);
+		r
+	}
+
+	fn intervals_square(isolation: Isolation, p: usize, q: usize, lambda: &Self, accu: i64) -> Vec<(Self, Self)> {
+		let bound = Self::spectral_bound(lambda);
 		if q == 1 {
-			return vec!((Self::from(-4), Self::from(4)))
+			return vec!((-bound.clone(), bound))
 		}
-		let pol = Self::trq(p, q, accu);
-		let eps = Self::eps(accu);
-		let mut r = if q % 2 == 0 {
+		let pol = Self::trq(p, q, lambda, accu);
+		let (eps, tol) = (Self::eps(accu), Self::gcd_tolerance(accu));
+		let c = Self::trace_bound(q, lambda, accu);
+		let mut r = if q.is_multiple_of(2) {
 			let mut v = pol.into_vec();
-			v[0] = Self::from(if q % 4 == 0 {8} else {-8});
+			let c2 = c.clone() + c;
+			v[0] = if q.is_multiple_of(4) {c2} else {-c2};
 			for i in 1..=q/2 {
 				v.swap(i, 2*i);
 			}
 			v.truncate(q/2+1);
 			let p1 = Polynomial::from(v.clone());
-			let mut r1 = p1.find_roots(Self::zero(), Self::from(16), &eps);
+			let bound2 = bound.clone() * bound;
+			let mut r1 = p1.find_roots_by(isolation, Self::zero(), bound2.clone(), &eps, &tol);
 			v.remove(0);
 			let p2 = Polynomial::from(v);
-			let mut r2 = p2.find_roots(Self::zero(), Self::from(16), &eps);
+			let mut r2 = p2.find_roots_by(isolation, Self::zero(), bound2, &eps, &tol);
 			let mut r = vec![Self::zero()];
 			r.append(&mut r1);
 			r.append(&mut r2);
 			r.into_iter().map(|x| x.sqrt()).collect()
 		} else {
 			let mut v = pol.into_vec();
-			v[0] = Self::from(4);
+			v[0] = c;
 			for i in 1..=q/2 {
 				v[2*i] = Self::zero();
 			}
-			Polynomial::from(v).find_roots(Self::from(-4), Self::from(4), &eps)
+			Polynomial::from(v).find_roots_by(isolation, -bound.clone(), bound, &eps, &tol)
 		};
-		r = r.into_iter().map(|x| x.accu(accu)).collect();
-		let mut nr = r.iter().map(|x| -x.clone()).collect();
-		r.append(&mut nr);
+		r = Self::mirror_edges(Lattice::Square, r.into_iter().map(|x| x.accu(accu)).collect());
 		r.sort_by(|a, b| a.partial_cmp(b).unwrap());
 		let mut r2 = Vec::new();
 		let mut ri = r.into_iter();
@@ -70,27 +280,386 @@ pub trait Appr: Decimal {
 		r2
 	}
 	
-	fn intervals_auto(p: usize, q: usize, accu: i64) -> (Vec<(Self, Self)>, Option<i64>) {
-		let mut int = vec![];
+// zigzag chains m of the honeycomb lattice with flux p/q per hexagon:
+// Ea(m) = c(m)b(m) + tb(m-1), Eb(m) = c(m)a(m) + ta(m+1), c(m) = 2cos(πmp/q + κ), t = λ/2 between the chains;
+// eliminating b gives (ε - c(m)^2 - t^2)a(m) = tc(m)a(m+1) + tc(m-1)a(m-1) in ε = E^2,
+// whose trace is returned without its κ-dependent constant term
+	fn trq_honeycomb(p: usize, q: usize, lambda: &Self, accu: i64) -> Polynomial<Self> {
+		let t = lambda.clone() / Self::from(2);
+		let c = (0..=q).map(|m| Self::cos_rational_x2((m*p) as i64, q as i64, accu)).collect::<Vec<_>>();
+		let h = |m: usize| (c[if m == 0 {q} else {m}].clone() * t.clone()).accu(accu);
+		let qm = c.iter().enumerate().skip(1).map(|(m, cm)| {
+			let d = (cm.clone() * cm.clone() + t.clone() * t.clone()).accu(accu);
+			Matrix::new((
+				(
+					Polynomial::from(vec![-d, Self::one()]),
+					Polynomial::from(vec![-h(m-1)])
+				),
+				(Polynomial::from(vec![h(m)]), Polynomial::zero())
+			))
+		}).collect::<Vec<_>>();
+		let growth = Self::from(5) + t.clone() * t.clone() + t.abs() * Self::from(2);
+		let mut v = Self::transfer_product(&qm, &growth, accu).trace().into_vec();
+		v[0] = Self::zero();
+		v.into_iter().map(|x| if x.is_one() || x.is_zero() {x} else {x.accu(accu)}).collect::<Vec<_>>().into()
+	}
+
+// the κ-dependent constant is (-1)^q(t^2q + y^2) with the scale of the transfer matrix t^q y, y ∈ [0, 2],
+// so the bands are (max(0, t^q - 2))^2 <= (-1)^(q+1) R(ε) <= (2 + t^q)^2
+	fn intervals_honeycomb(isolation: Isolation, p: usize, q: usize, lambda: &Self, accu: i64) -> Vec<(Self, Self)> {
+		let t = lambda.abs() / Self::from(2);
+		let bound = Self::from(2) + t.clone();
+		let window = bound.clone() * bound + Self::one();
+		let (eps, tol) = (Self::eps(accu), Self::gcd_tolerance(accu));
+		let mut tau = Self::one();
+		for _ in 0..q {
+			tau = (tau * t.clone()).accu(accu);
+		}
+		let sign = if q.is_multiple_of(2) {Self::one()} else {-Self::one()};
+		let pol = Self::trq_honeycomb(p, q, lambda, accu);
+		let hi = (Self::from(2) + tau.clone()) * (Self::from(2) + tau.clone());
+		let mut r = (pol.clone() + sign.clone() * hi).find_roots_by(isolation, Self::zero(), window.clone(), &eps, &tol);
+		if tau > Self::from(2) {
+			let lo = (tau.clone() - Self::from(2)) * (tau - Self::from(2));
+			r.append(&mut (pol + sign * lo).find_roots_by(isolation, Self::zero(), window, &eps, &tol));
+		} else {
+			let mut v = pol.into_vec();
+			v.remove(0);
+			r.append(&mut Polynomial::from(v).find_roots_by(isolation, Self::zero(), window, &eps, &tol));
+			r.push(Self::zero());
+		}
+		r.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let mut r2 = Vec::new();
+		let mut ri = r.into_iter();
+		while let (Some(e1), Some(e2)) = (ri.next(), ri.next()) {
+			let (x1, x2) = (e1.sqrt().accu(accu), e2.sqrt().accu(accu));
+			if x1.is_zero() {
+				r2.push((-x2.clone(), x2));
+			} else {
+				r2.push((-x2.clone(), -x1.clone()));
+				r2.push((x1, x2));
+			}
+		}
+		r2.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+		r2
+	}
+
+// triangular lattice as the square lattice with one diagonal, flux p/q per square (two triangles);
+// a gauge transformation combines the horizontal and diagonal bonds into real hoppings,
+// h(m)ψ(m+1) + h(m-1)ψ(m-1) + λcos(2πmp/q + 2κ)ψ(m) = Eψ(m), h(m) = 2cos(π(2m+1)p/(2q) + κ),
+// with h(0) replaced by h(q) to make the chain q-periodic;
+// the trace is returned at the phase κ where h(q) = 0, which leaves its κ-independent part P(E)
+	fn trq_triangular(p: usize, q: usize, lambda: &Self, accu: i64) -> Polynomial<Self> {
+		let half = lambda.clone() / Self::from(2);
+		let (p, q) = (p as i64, q as i64);
+		let h = |m: i64| if m % q == 0 {Self::zero()} else {Self::cos_rational_x2(q + 2*p*(m - q), 2*q, accu)};
+		let qm = (1..=q).map(|m| {
+			let d = (Self::cos_rational_x2(q - p + 2*p*m, q, accu) * half.clone()).accu(accu);
+			Matrix::new((
+				(
+					Polynomial::from(vec![-d, Self::one()]),
+					Polynomial::from(vec![-h(m-1)])
+				),
+				(Polynomial::from(vec![h(m)]), Polynomial::zero())
+			))
+		}).collect::<Vec<_>>();
+		let v = Self::transfer_product(&qm, &(Self::from(3) + lambda.abs()), accu).trace().into_vec();
+		v.into_iter().map(|x| if x.is_one() || x.is_zero() {x} else {x.accu(accu)}).collect::<Vec<_>>().into()
+	}
+
+// the trace is P(E) + ay^2 with a = (-1)^p(-λ/2)^q and the scale of the transfer matrix y ∈ [0, 2],
+// so the bands are -max(2y + ay^2) <= P(E) <= max(2y - ay^2);
+// there is no E -> -E symmetry, so the band edges are all found directly
+	fn intervals_triangular(isolation: Isolation, p: usize, q: usize, lambda: &Self, accu: i64) -> Vec<(Self, Self)> {
+		let window = Self::spectral_bound(lambda) + Self::from(3);
+		let (eps, tol) = (Self::eps(accu), Self::gcd_tolerance(accu));
+		let mut a = if p.is_multiple_of(2) {Self::one()} else {-Self::one()};
+		for _ in 0..q {
+			a = (a * lambda.clone() / Self::from(-2)).accu(accu);
+		}
+		let reach = |a: Self| if a.clone() * Self::from(2) <= -Self::one() {-(Self::one() / a).accu(accu)} else {Self::from(4) + Self::from(4) * a};
+		let pol = Self::trq_triangular(p, q, lambda, accu);
+		let mut r = (pol.clone() - reach(-a.clone())).find_roots_by(isolation, -window.clone(), window.clone(), &eps, &tol);
+		r.append(&mut (pol + reach(a)).find_roots_by(isolation, -window.clone(), window, &eps, &tol));
+		r = r.into_iter().map(|x| x.accu(accu)).collect();
+		r.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let mut r2 = Vec::new();
+		let mut ri = r.into_iter();
+		while let (Some(x1), Some(x2)) = (ri.next(), ri.next()) {
+			r2.push((x1, x2));
+		}
+		r2
+	}
+
+// numbers of bands r below the gaps between consecutive intervals, so that the integrated density of states
+// in the gap is r/q; if a symmetric spectrum is one interval short, the interval containing E = 0
+// holds two touching bands, otherwise the bands cannot be counted
+	fn gap_counts(q: usize, lattice: Lattice, intervals: &[(Self, Self)]) -> Option<Vec<usize>> {
+		let n = lattice.bands(q);
+		let merged = if intervals.len() == n {
+			None
+		} else if intervals.len() + 1 == n && lattice.is_symmetric() {
+			Some(intervals.iter().position(|(l, r)| !l.is_positive() && !r.is_negative())?)
+		} else {
+			return None
+		};
+		let mut r = 0;
+		Some((0..intervals.len() - 1).map(|i| {
+			r += if merged == Some(i) {2} else {1};
+			r
+		}).collect())
+	}
+
+// (σ, s) of the gaps between consecutive intervals
+	fn gap_labels(p: usize, q: usize, lattice: Lattice, intervals: &[(Self, Self)]) -> Option<Vec<(i64, i64)>> {
+		Some(Self::gap_counts(q, lattice, intervals)?.into_iter().map(|r| tknn(p, q, r)).collect())
+	}
+
+	fn intervals_auto(p: usize, q: usize, lattice: Lattice, lambda: &Self, accu: i64) -> (Vec<(Self, Self)>, Option<i64>) {
+		Self::intervals_auto_by(Isolation::Sturm, p, q, lattice, lambda, accu)
+	}
+
+	fn intervals_auto_by(isolation: Isolation, p: usize, q: usize, lattice: Lattice, lambda: &Self, accu: i64) -> (Vec<(Self, Self)>, Option<i64>) {
+		let mut int = Self::intervals_by(isolation, p, q, lattice, lambda, accu);
 		for accu2 in accu..accu+20 {
-			int = Self::intervals(p, q, accu2);
 			let vl = int.len();
-			if vl == q || vl == q - 1 && q % 2 == 0 {
+			if lattice == Lattice::Square && (vl == q || vl == q - 1 && q.is_multiple_of(2)) {
 				return (int, Some(accu2))
 			}
+			let int2 = Self::intervals_by(isolation, p, q, lattice, lambda, accu2 + 1);
+			if lattice != Lattice::Square && int2.len() == vl {
+				return (int, Some(accu2))
+			}
+			int = int2;
 		}
 		(int, None)
 	}
 
+	// generalized Harper equation
+	// b(m)ψ(m+1) + b(m-1)ψ(m-1) + t2(ψ(m+2) + ψ(m-2)) + d(m)ψ(m) = Eψ(m)
+	// with d(m) = λcos(θ_m), b(m) = 1 + 2t'cos(θ_{m+1/2}) at phase ν = kπ/(2qn);
+	// the transfer matrices are scaled to have polynomial entries:
+	// by b(m) for t2 = 0 (2×2) and by t2 otherwise (4×4)
+	fn harper_terms(p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, (k, n): (i64, i64), accu: i64) -> (Vec<Self>, Vec<Self>) {
+		let half = lambda.clone() / Self::from(2);
+		let (p, q) = (p as i64, q as i64);
+		let d = (0..=q).map(|m| (Self::cos_rational_x2(4*n*m*p + k, 2*q*n, accu) * half.clone()).accu(accu)).collect();
+		let b = (0..=q).map(|m| Self::one() + (Self::cos_rational_x2(2*n*(2*m+1)*p + k, 2*q*n, accu) * hop.diagonal.clone()).accu(accu)).collect();
+		(d, b)
+	}
+
+	fn trq_nnn(p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, phase: (i64, i64), accu: i64) -> matrix::Matrix<Polynomial<Self>> {
+		let (d, b) = Self::harper_terms(p, q, lambda, hop, phase, accu);
+		let c = |v: &Self| Polynomial::from(vec![v.clone()]);
+		let x = Polynomial::monomial(1, Self::one());
+		let mut qq = matrix::Matrix::one(if hop.second.is_zero() {2} else {4});
+		for m in 1..=q {
+			let e = x.clone() - d[m].clone();
+			let qm = if hop.second.is_zero() {
+				matrix::Matrix::new(vec![
+					vec![e, -c(&b[m-1])],
+					vec![c(&b[m]), Polynomial::zero()],
+				])
+			} else {
+				let (t2, z) = (c(&hop.second), Polynomial::zero());
+				matrix::Matrix::new(vec![
+					vec![-c(&b[m]), e, -c(&b[m-1]), -t2.clone()],
+					vec![t2.clone(), z.clone(), z.clone(), z.clone()],
+					vec![z.clone(), t2.clone(), z.clone(), z.clone()],
+					vec![z.clone(), z.clone(), t2, z],
+				])
+			};
+			qq = &qm * &qq;
+		}
+		qq
+	}
+
+// None if no sampled phase gave the full set of bands, which takes more accuracy (with t2 != 0)
+	fn intervals_nnn(p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, accu: i64) -> Option<Vec<(Self, Self)>> {
+		Self::intervals_nnn_by(Isolation::Sturm, p, q, lambda, hop, accu)
+	}
+
+	fn intervals_nnn_by(isolation: Isolation, p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, accu: i64) -> Option<Vec<(Self, Self)>> {
+		if hop.is_zero() {
+			return Some(Self::intervals_square(isolation, p, q, lambda, accu))
+		}
+// beyond the spectrum, so that no edge is at the end of the search
+		let bound = Self::spectral_bound(lambda) + Self::from(4) * hop.diagonal.abs() + Self::from(2) * hop.second.abs() + Self::one();
+		let (eps, tol) = (Self::eps(accu), Self::gcd_tolerance(accu));
+		let mut edges = if hop.second.is_zero() {
+// generalized Chambers relation: only the constant term of the trace depends on the phase u = qν,
+// tr = P(E) + Gc cos u + Gs sin u, and so does the scale ∏b(m) = B0 + Hc cos u + Hs sin u;
+// the bands are lo <= P(E) <= hi, where lo and hi are the extrema of ∓2|∏b(m)| - tr + P(E) over u
+			let mut traces = (0..3).map(|k| Self::trq_nnn(p, q, lambda, hop, (k, 1), accu).trace().into_vec()).collect::<Vec<_>>();
+			let constants = traces.iter().enumerate().map(|(k, tr)| {
+				let scale = Self::harper_terms(p, q, lambda, hop, (k as i64, 1), accu).1.into_iter().skip(1).fold(Self::one(), |a, b| (a * b).accu(accu));
+				(tr[0].clone(), scale)
+			}).collect::<Vec<_>>();
+			let two = Self::from(2);
+			let p0 = (constants[0].0.clone() + constants[2].0.clone()) / two.clone();
+			let (gc, gs) = ((constants[0].0.clone() - constants[2].0.clone()) / two.clone(), constants[1].0.clone() - p0.clone());
+			let b0 = (constants[0].1.clone() + constants[2].1.clone()) / two.clone();
+			let (hc, hs) = ((constants[0].1.clone() - constants[2].1.clone()) / two.clone(), constants[1].1.clone() - b0.clone());
+			let amp = |a: Self, b: Self| (a.clone() * a + b.clone() * b).sqrt().accu(accu);
+			let max = |a: Self, b: Self| if a > b {a} else {b};
+			let (h2c, h2s, b2) = (hc * two.clone(), hs * two.clone(), b0 * two);
+			let hi = max(b2.clone() + amp(h2c.clone() - gc.clone(), h2s.clone() - gs.clone()), -b2.clone() + amp(h2c.clone() + gc.clone(), h2s.clone() + gs.clone()));
+			let lo = -max(b2.clone() + amp(h2c.clone() + gc.clone(), h2s.clone() + gs.clone()), -b2 + amp(h2c - gc, h2s - gs));
+			let mut v = traces.remove(1);
+			v[0] = p0;
+			let pol = Polynomial::from(v);
+			let mut r = (pol.clone() - hi).find_roots_by(isolation, -bound.clone(), bound.clone(), &eps, &tol);
+			r.append(&mut (pol - lo).find_roots_by(isolation, -bound.clone(), bound, &eps, &tol));
+			r
+		} else {
+// no Chambers-type relation: the range of every band is extremized over the phase,
+// starting from sampled phases and refining the best ones on a dyadic grid
+			let mut cache = std::collections::HashMap::new();
+			let mut bands = |(mut k, mut n): (i64, i64)| {
+				while n > 1 && k % 2 == 0 {
+					k /= 2;
+					n /= 2;
+				}
+				cache.entry((k.rem_euclid(4*n), n)).or_insert_with(|| Self::chain_bands(isolation, p, q, lambda, hop, (k, n), &bound, accu)).clone()
+			};
+			let samples = (0..4*NNN_PHASES).filter_map(|k| bands((k, NNN_PHASES)).map(|b| (k, b))).collect::<Vec<_>>();
+			let mut r = vec![];
+			for j in 0..q {
+				for upper in [false, true] {
+					let edge = |b: &Vec<(Self, Self)>| if upper {-b[j].1.clone()} else {b[j].0.clone()};
+					let (mut k, mut best) = samples.iter().map(|(k, b)| (*k, edge(b))).fold(None, |a: Option<(i64, Self)>, x| match a {
+						Some(a) if a.1 <= x.1 => Some(a),
+						_ => Some(x),
+					})?;
+					let mut n = NNN_PHASES;
+					while 8 * n * (q as i64) < i64::from(i32::MAX) {
+						k *= 2;
+						n *= 2;
+						let near = [-1, 1].iter().filter_map(|dk| bands((k + dk, n)).map(|b| (k + dk, edge(&b)))).collect::<Vec<_>>();
+						if near.iter().all(|(_, e)| (e.clone() - best.clone()).abs() < eps) {
+							break
+						}
+						for (k2, e) in near {
+							if e < best {
+								k = k2;
+								best = e;
+							}
+						}
+					}
+					r.push((j, if upper {-best} else {best}));
+				}
+			}
+			let mut ranges = r.chunks(2).map(|c| (c[0].1.clone(), c[1].1.clone())).collect::<Vec<_>>();
+			ranges.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+			let mut r = vec![];
+			for (l, h) in ranges {
+				match r.last_mut() {
+					Some(last) if l <= *last => if h > *last {*last = h},
+					_ => {
+						r.push(l);
+						r.push(h);
+					}
+				}
+			}
+			r
+		};
+		edges = edges.into_iter().map(|x| x.accu(accu)).collect();
+		edges.sort_by(|a, b| a.partial_cmp(b).unwrap());
+		let mut r2 = Vec::new();
+		let mut ei = edges.into_iter();
+		while let (Some(x1), Some(x2)) = (ei.next(), ei.next()) {
+			r2.push((x1, x2));
+		}
+		Some(r2)
+	}
+
+	// ranges of the q bands of a single chain with t2 != 0 at phase ν = kπ/(2qn);
+	// the Bloch factors z = exp(iθ) of the unimodular transfer matrix T solve z^4 - T1 z^3 + T2 z^2 - T1 z + 1 = 0,
+	// i.e. w^2 - T1 w + T2 - 2 = 0 for w = 2cos θ, so the bands are bounded by their energies at w = ±2
+	// and by the extrema inside the Brillouin zone, where the two roots w coincide
+	#[allow(clippy::too_many_arguments)]
+	fn chain_bands(isolation: Isolation, p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, phase: (i64, i64), bound: &Self, accu: i64) -> Option<Vec<(Self, Self)>> {
+		let (eps, tol) = (Self::eps(accu), Self::gcd_tolerance(accu));
+		let tm = Self::trq_nnn(p, q, lambda, hop, phase, accu);
+		let mut s = Self::one();
+		for _ in 0..q {
+			s = s * hop.second.clone();
+		}
+		let scale = |pol: Polynomial<Self>, s: &Self| Polynomial::from(pol.into_vec().into_iter().map(|x| (x / s.clone()).accu(accu)).collect::<Vec<_>>());
+		let t1 = tm.trace();
+		let t2 = scale(t1.clone() * t1.clone() - (&tm * &tm).trace(), &(s.clone() * s.clone() * Self::from(2)));
+		let t1 = scale(t1, &s);
+		let at = |w: Self| t2.clone() - t1.clone() * w.clone() + (w.clone() * w - Self::from(2));
+		let e0 = at(Self::from(2)).find_roots_by(isolation, -bound.clone(), bound.clone(), &eps, &tol);
+		let epi = at(Self::from(-2)).find_roots_by(isolation, -bound.clone(), bound.clone(), &eps, &tol);
+		if e0.len() != q || epi.len() != q {
+			return None
+		}
+		let mut r = e0.into_iter().zip(epi).map(|(a, b)| if a < b {(a, b)} else {(b, a)}).collect::<Vec<_>>();
+		let disc = t1.clone() * t1.clone() - t2.clone() * Self::from(4) + Self::from(8);
+		for e in disc.find_roots_by(isolation, -bound.clone(), bound.clone(), &eps, &tol) {
+			let w = t1.eval_ref(&e) / Self::from(2);
+			if w.abs() > Self::from(2) {
+				continue
+			}
+			let ew = at(w).find_roots_by(isolation, -bound.clone(), bound.clone(), &eps, &tol);
+			if ew.len() != q {
+				continue
+			}
+			let j = (0..q).min_by(|&i, &j| (ew[i].clone() - e.clone()).abs().partial_cmp(&(ew[j].clone() - e.clone()).abs()).unwrap()).unwrap();
+			if e < r[j].0 {
+				r[j].0 = e;
+			} else if e > r[j].1 {
+				r[j].1 = e;
+			}
+		}
+		Some(r)
+	}
+
+// the bands may overlap, so there is no expected number of intervals: the accuracy is taken as sufficient
+// when one more digit gives as many intervals, at most q, which is likely but not proven to be right
+	fn intervals_nnn_auto(p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, accu: i64) -> (Vec<(Self, Self)>, Option<i64>) {
+		Self::intervals_nnn_auto_by(Isolation::Sturm, p, q, lambda, hop, accu)
+	}
+
+	fn intervals_nnn_auto_by(isolation: Isolation, p: usize, q: usize, lambda: &Self, hop: &Hopping<Self>, accu: i64) -> (Vec<(Self, Self)>, Option<i64>) {
+		if hop.is_zero() {
+			return Self::intervals_auto_by(isolation, p, q, Lattice::Square, lambda, accu)
+		}
+		let mut int = Self::intervals_nnn_by(isolation, p, q, lambda, hop, accu);
+		for accu2 in accu..accu+20 {
+			let int2 = Self::intervals_nnn_by(isolation, p, q, lambda, hop, accu2 + 1);
+			match (int, &int2) {
+				(Some(int), Some(int2)) if int.len() == int2.len() && int.len() <= q => return (int, Some(accu2)),
+				_ => int = int2,
+			}
+		}
+		(int.unwrap_or_default(), None)
+	}
+
 	fn eps(_: i64) -> Self;
+// relative tolerance of the gcds that split off multiple roots: a remainder whose coefficients are below it,
+// relative to those of the dividend, is taken for rounding noise. Apart from eps, the accuracy of the roots
+	fn gcd_tolerance(accu: i64) -> Self {
+		Self::eps(accu)
+	}
 	fn accu(self, _: i64) -> Self;
 	fn sqrt(self) -> Self;
 	fn cos_rational_x2(p: i64, q: i64, accu: i64) -> Self;
+// 2cos(πp/q) together with a bound on its error
+	fn cos_rational_x2_err(p: i64, q: i64, accu: i64) -> (Self, Self) {
+		(Self::cos_rational_x2(p, q, accu), Self::eps(accu))
+	}
+// whether the products of the transfer matrices, taken along a balanced tree, multiply their entries by Karatsuba's method
+	const KARATSUBA: bool = true;
+// bound on the rounding error of an arithmetic operation with the result x, zero for exact arithmetic
+	fn rounding_error(_x: &Self) -> Self {
+		Self::zero()
+	}
 	fn reduce_args(p: i64, q: i64) -> (u32, u32, i8) {
 		assert!(q != 0);
-		let mut p: u32 = p.abs() as u32;
-		let q: u32 = q.abs() as u32;
-		p %= 2 * q;
+		let q: u32 = q.unsigned_abs() as u32;
+		let mut p: u32 = (p.unsigned_abs() % (2 * q as u64)) as u32;
 		let mut sign = if p >= q {
 			p -= q;
 			-1
@@ -112,6 +681,11 @@ impl Appr for f64 {
 		1e-14
 	}
 
+// a few dozen roundings of the coefficients
+	fn gcd_tolerance(_accu: i64) -> Self {
+		64.0 * f64::EPSILON
+	}
+
 	fn sqrt(self) -> Self {
 		self.sqrt()
 	}
@@ -120,6 +694,26 @@ impl Appr for f64 {
 		let (p, q, sign) = Self::reduce_args(p, q);
 		2.0 * (std::f64::consts::PI * p as f64 / q as f64).cos() * sign as f64
 	}
+
+// the rounding errors of π, of the argument and of the cosine, as for Interval
+	fn cos_rational_x2_err(p: i64, q: i64, accu: i64) -> (f64, f64) {
+		(Self::cos_rational_x2(p, q, accu), 4e-15)
+	}
+
+	fn rounding_error(x: &Self) -> Self {
+		x.abs() * f64::EPSILON
+	}
+}
+
+// m_q ⋯ m_1 along a balanced tree, the entries multiplied by Karatsuba's method or term by term,
+// and the coefficients of every partial product rounded by round
+fn product_tree<C, R>(qm: &[Matrix<Polynomial<C>>], karatsuba: bool, round: R) -> Matrix<Polynomial<C>>
+	where C: Zero + Clone + std::ops::Sub<Output=C> + std::ops::Mul<Output=C>, R: Fn(C) -> C
+{
+	Matrix::product_by(qm, &|a: &Polynomial<C>, b: &Polynomial<C>| {
+		let ab = if karatsuba {a.karatsuba(b)} else {a.clone() * b.clone()};
+		ab.into_vec().into_iter().map(&round).collect::<Vec<_>>().into()
+	})
 }
 
 fn with_accu(bd: BigDecimal, accu: i64) -> BigDecimal {
@@ -133,6 +727,7 @@ fn with_accu_ref(bd: &BigDecimal, accu: i64) -> BigDecimal {
 }
 
 mod pi;
+mod cos_table;
 
 impl Appr for BigDecimal {
 	fn accu(self, p: i64) -> Self {
@@ -144,34 +739,62 @@ impl Appr for BigDecimal {
 	}
 
 	fn sqrt(self) -> Self {
-		(&self).sqrt().unwrap()
+		BigDecimal::sqrt(&self).unwrap()
 	}
 
 	fn cos_rational_x2(p: i64, q: i64, accu: i64) -> BigDecimal {
+		Self::cos_rational_x2_err(p, q, accu).0
+	}
+
+// from the table of all 2cos(πp/q) for the reduced q (see cos_table), which the sweeps share
+	fn cos_rational_x2_err(p: i64, q: i64, accu: i64) -> (BigDecimal, BigDecimal) {
 		let (p, q, sign) = Self::reduce_args(p, q);
-// Sec. 4.1 of https://www.mpfr.org/algorithms.pdf
-// roundings are currently done to the nearest digit, not up ^ or down V as required
-// also, k is set to 0 for simplicity
-		let waccu = if accu > 0 {accu + 2} else {2};
-		let pi = BigDecimal::parse_bytes(&pi::DEC[..(waccu as usize + 1)], 10).unwrap();
-		let p = BigDecimal::from(p);
-		let q = BigDecimal::from(q);
-		let x = pi * p / q;
-		let r = with_accu(x.square(), waccu); // ^
-		let mut s = BigDecimal::one();
-		let mut t = BigDecimal::one();
-		for l in 1.. {
-			if t.digits() as i64 - t.as_bigint_and_exponent().1 < -waccu {
+		let (c, err) = cos_table::get(q, accu)[p as usize].clone();
+		(if sign == 1 {c} else {-c}, err)
+	}
+}
+
+// enclosure [lo, hi]·10^-w of cos(πp/q) for 0 < p/q < 1/2, after Sec. 4.1 of https://www.mpfr.org/algorithms.pdf
+// with every rounding directed (V down, ^ up): the argument x is halved k times, cos(x/2^k) is bounded by partial sums
+// of its alternating Taylor series, and cos(2y) = 2cos(y)^2 - 1, increasing in cos(y) >= 0, is applied k times
+fn cos_enclosure(p: u32, q: u32, w: usize, k: u32) -> (BigInt, BigInt) {
+	let one = (0..w).fold(BigInt::from(1), |a, _| a * 10);
+	let ceil_div = |a: BigInt, b: &BigInt| (a + b - 1) / b;
+	let pi = pi::floor(w);
+	let halvings = BigInt::from(1u64 << k);
+	let x_lo = pi.clone() * p / q / &halvings; // V
+	let x_hi = ceil_div(ceil_div((pi + 1) * p, &BigInt::from(q)), &halvings); // ^
+// the terms y^2l/(2l)! decrease, so the partial sums ending with a subtracted term are below the cosine
+// and those ending with an added term above it
+	let series = |y: &BigInt, upper: bool| {
+		let y2 = y * y;
+		let (mut t_lo, mut t_hi, mut s) = (one.clone(), one.clone(), one.clone());
+		for l in 1u32.. {
+			let d = one.clone() * &one * (2*l - 1) * (2*l);
+			t_lo = t_lo * &y2 / &d; // V
+			t_hi = ceil_div(t_hi * &y2, &d); // ^
+			let odd = l % 2 == 1;
+			match (odd, upper) {
+				(true, false) => s -= &t_hi,
+				(true, true) => s -= &t_lo,
+				(false, false) => s += &t_lo,
+				(false, true) => s += &t_hi,
+			}
+			if t_hi <= BigInt::from(1) && odd != upper {
 				break
 			}
-			t = with_accu(t * &r, waccu); // ^
-			t = t / (2*l*(2*l-1)); // ^
-			s = if l%2 == 0 { s + &t } else { s - &t }; // V
 		}
-		s = s.double();
-		s = if sign == 1 { s } else { -s };
-		with_accu(s + BigDecimal::one(), accu) - BigDecimal::one()
+		s
+	};
+	let (mut lo, mut hi) = (series(&x_hi, false), series(&x_lo, true));
+	for _ in 0..k {
+		if lo < BigInt::zero() {
+			lo = BigInt::zero();
+		}
+		lo = lo.clone() * &lo * 2 / &one - &one; // V
+		hi = ceil_div(hi.clone() * &hi * 2, &one) - &one; // ^
 	}
+	(lo.max(BigInt::zero()), hi)
 }
 
 #[cfg(test)]
@@ -185,6 +808,171 @@ mod tests {
 		assert_eq!(BigDecimal::cos_rational_x2(1, 1, 10), BigDecimal::from(-2));
 		assert_eq!(BigDecimal::cos_rational_x2(1, 2, 10), BigDecimal::zero());
 		assert_eq!(BigDecimal::cos_rational_x2(1, 3, 10), BigDecimal::one());
+		assert_eq!(BigDecimal::cos_rational_x2(1, 4, 10), "1.4142135624".parse().unwrap());
+		assert_eq!(BigDecimal::cos_rational_x2(-3, 4, 5), "-1.41421".parse().unwrap());
+	}
+
+	#[test]
+	fn cos_error_bound() {
+		for &(p, q) in &[(1, 4), (1, 5), (2, 7), (5, 12), (7, 9), (1, 1000), (499, 1000)] {
+			for &accu in &[0, 3, 10, 40] {
+				let (c, err) = BigDecimal::cos_rational_x2_err(p, q, accu);
+				let exact = BigDecimal::cos_rational_x2(p, q, 200);
+				assert!((c - exact).abs() <= err && err <= BigDecimal::eps(accu), "{}/{} {}: {}", p, q, accu, err);
+			}
+		}
+		assert_eq!(BigDecimal::cos_rational_x2_err(2, 3, 10), (BigDecimal::from(-1), BigDecimal::zero()));
+		// beyond the 1000 digits of π that used to be tabulated
+		let (c, err) = BigDecimal::cos_rational_x2_err(1, 7, 1100);
+		assert!(err <= BigDecimal::eps(1100) && (c - BigDecimal::cos_rational_x2(1, 7, 1120)).abs() <= err);
+	}
+
+	#[test]
+	fn trq_error_bound() {
+		for &(p, q, lambda) in &[(1, 3, "2"), (2, 5, "1.3"), (3, 8, "2"), (5, 13, "3")] {
+			let lambda: BigDecimal = lambda.parse().unwrap();
+			let (pol, err) = BigDecimal::trq_with_error(p, q, &lambda, 12);
+			assert_eq!(pol, BigDecimal::trq(p, q, &lambda, 12));
+			let exact = BigDecimal::trq(p, q, &lambda, 60);
+			assert!(pol.as_ref().iter().zip(exact.as_ref()).zip(&err).all(|((a, b), e)| (a - b).abs() <= *e));
+		}
+		// even q, where E = 0 is a double root: the edges at the accuracy of 'd' stay those of f64
+		for &(p, q) in &[(1, 6), (1, 12), (5, 12)] {
+			let accu = q as i64 * 3 / 4 + 2;
+			let d = BigDecimal::intervals(p, q, Lattice::Square, &2.into(), accu);
+			let f = f64::intervals(p, q, Lattice::Square, &2.0, 0);
+			let edges = |int: &[(f64, f64)]| int.iter().flat_map(|&(l, r)| [l, r]).collect::<Vec<_>>();
+			let d = edges(&d.iter().map(|(l, r)| (l.to_string().parse().unwrap(), r.to_string().parse().unwrap())).collect::<Vec<_>>());
+			assert!(d.len() == edges(&f).len() && d.iter().all(|x| x.abs() <= 4.0), "{}/{}", p, q);
+			assert!(d.iter().zip(edges(&f)).all(|(x, y)| (x - y).abs() < 10f64.powi(3 - accu as i32)), "{}/{}", p, q);
+		}
+		let (pol, err) = f64::trq_with_error(2, 5, &1.3, 0);
+		assert_eq!(pol, f64::trq(2, 5, &1.3, 0));
+		let exact = BigDecimal::trq(2, 5, &"1.3".parse().unwrap(), 30);
+		assert!(pol.as_ref().iter().zip(exact.as_ref()).zip(&err).all(|((a, b), e)| (a - b.to_string().parse::<f64>().unwrap()).abs() <= *e && *e < 1e-12));
+	}
+	
+	#[test]
+	fn aubry_duality() {
+		for &(p, q) in &[(1, 3), (1, 4), (2, 5), (3, 8)] {
+			let i1 = f64::intervals(p, q, Lattice::Square, &1.0, 0);
+			let i4 = f64::intervals(p, q, Lattice::Square, &4.0, 0);
+			assert_eq!(i1.len(), i4.len());
+			for ((l1, r1), (l4, r4)) in i1.into_iter().zip(i4) {
+				assert!((2.0 * l1 - l4).abs() < 1e-10 && (2.0 * r1 - r4).abs() < 1e-10);
+			}
+		}
+		assert_eq!(f64::intervals(0, 1, Lattice::Square, &1.0, 0), vec![(-3.0, 3.0)]);
+		// λ -> -λ shifts the phase by π, which leaves the union of the bands unchanged, also for odd q
+		for &(p, q) in &[(1, 3), (2, 5), (1, 4)] {
+			let a = f64::intervals(p, q, Lattice::Square, &1.5, 0);
+			let b = f64::intervals(p, q, Lattice::Square, &-1.5, 0);
+			assert!(a.len() == b.len() && a.iter().zip(&b).all(|(x, y)| (x.0 - y.0).abs() < 1e-10 && (x.1 - y.1).abs() < 1e-10), "{}/{}", p, q);
+		}
+	}
+	
+	#[test]
+	fn matrix_backend() {
+		for q in 1..=8 {
+			for p in (0..=q/2).filter(|&p| (1..=p).all(|d| d == 1 || p % d != 0 || q % d != 0) && (p > 0 || q == 1)) {
+				for &lambda in &[2.0, 1.3, 3.0] {
+					let a = f64::intervals_square(Isolation::Sturm, p, q, &lambda, 0);
+					let b = f64::intervals_matrix(p, q, &lambda, 0);
+					assert_eq!(a.len(), b.len());
+					assert!(a.iter().zip(&b).all(|(x, y)| (x.0 - y.0).abs() < 1e-9 && (x.1 - y.1).abs() < 1e-9), "{}/{} {:?} {:?}", p, q, a, b);
+				}
+			}
+		}
+		let b = BigDecimal::intervals_matrix(2, 5, &2.into(), 10);
+		let a = f64::intervals_matrix(2, 5, &2.0, 0);
+		assert!(a.into_iter().zip(b).all(|(x, y)| (x.0 - y.0.to_string().parse::<f64>().unwrap()).abs() < 1e-9));
+		// far beyond the reach of the f64 characteristic polynomial
+		let b = f64::intervals_matrix(1, 101, &2.0, 0);
+		assert_eq!(b.len(), 101);
+		assert!(b.windows(2).all(|w| w[0].1 < w[1].0));
+	}
+	
+	#[test]
+	fn chambers() {
+		for &(p, q, lambda) in &[(1, 3, 2.0), (2, 5, 2.0), (3, 7, 1.5)] {
+			let mut edges = f64::bloch_energies(p, q, &lambda, (0, 1), (0, 1), 0);
+			assert_eq!(edges.len(), q);
+			edges.append(&mut f64::bloch_energies(p, q, &lambda, (1, q as i64), (1, q as i64), 0));
+			edges.sort_by(|a, b| a.partial_cmp(b).unwrap());
+			let int = f64::intervals(p, q, Lattice::Square, &lambda, 0);
+			assert!(int.into_iter().flat_map(|(l, r)| [l, r]).zip(edges).all(|(a, b)| (a - b).abs() < 1e-10));
+			let e = f64::bloch_energies(p, q, &lambda, (1, 5), (2, 7), 0);
+			assert_eq!(e.len(), q);
+			assert!(e.iter().sum::<f64>().abs() < 1e-10);
+			assert_eq!(e, f64::bloch_energies(p, q, &lambda, (-1, 5), (-2, 7), 0));
+		}
+		let e = f64::bloch_energies(1, 1, &3.0, (1, 3), (1, 4), 0);
+		assert!(e.len() == 1 && (e[0] - 1.0 - 1.5 * 2f64.sqrt()).abs() < 1e-12);
+	}
+	
+	#[test]
+	fn nnn() {
+		let close = |a: Vec<(f64, f64)>, b: Vec<(f64, f64)>, eps: f64| a.len() == b.len() && a.into_iter().zip(b).all(|(x, y)| (x.0 - y.0).abs() < eps && (x.1 - y.1).abs() < eps);
+		assert!(close(f64::intervals_nnn(0, 1, &2.0, &Hopping::new(0.2, 0.0), 0).unwrap(), vec![(-3.2, 4.8)], 1e-12));
+		assert!(close(f64::intervals_nnn(0, 1, &2.0, &Hopping::new(0.0, 0.1), 0).unwrap(), vec![(-3.8, 4.2)], 1e-12));
+		assert!(close(f64::intervals_nnn(1, 3, &2.0, &Hopping::new(0.2, 0.0), 0).unwrap(), vec![(-2.58564, -1.6), (-1.27846, 0.18564), (2.4, 2.87846)], 1e-5));
+		let r = vec![(-2.73205, -2.53623), (-1.08239, -0.4), (0.00784, 1.08239), (2.61313, 3.05991)];
+		assert!(close(f64::intervals_nnn(1, 4, &2.0, &Hopping::new(0.0, 0.2), 0).unwrap(), r.clone(), 1e-5));
+		let (int, accu) = BigDecimal::intervals_nnn_auto(1, 4, &2.into(), &Hopping::new(0.into(), "0.2".parse().unwrap()), 6);
+		assert!(accu.is_some() && close(int.into_iter().map(|(l, r)| (l.to_string().parse().unwrap(), r.to_string().parse().unwrap())).collect(), r, 1e-5));
+		let tr = f64::trq_nnn(2, 5, &1.5, &Hopping::new(0.0, 0.0), (-1, 1), 0).trace();
+		assert!(tr.as_ref().iter().zip(f64::trq(2, 5, &1.5, 0).as_ref()).all(|(a, b)| (a - b).abs() < 1e-12));
+	}
+	
+	#[test]
+	fn honeycomb() {
+		let close = |a: Vec<(f64, f64)>, b: Vec<(f64, f64)>, eps: f64| a.len() == b.len() && a.into_iter().zip(b).all(|(x, y)| (x.0 - y.0).abs() < eps && (x.1 - y.1).abs() < eps);
+		assert!(close(f64::intervals(0, 1, Lattice::Honeycomb, &2.0, 0), vec![(-3.0, 3.0)], 1e-12));
+		assert!(close(f64::intervals(1, 1, Lattice::Honeycomb, &6.0, 0), vec![(-5.0, -1.0), (1.0, 5.0)], 1e-12));
+		let s3 = 3f64.sqrt();
+		let r = f64::intervals(1, 3, Lattice::Honeycomb, &2.0, 0);
+		assert!(close(r.clone(), vec![(-2.53209, -6f64.sqrt()), (-s3, -1.34730), (-0.87939, 0.87939), (1.34730, s3), (6f64.sqrt(), 2.53209)], 1e-5));
+		assert_eq!(r, f64::intervals(2, 3, Lattice::Honeycomb, &2.0, 0));
+		assert!(close(f64::intervals(2, 5, Lattice::Honeycomb, &2.6, 0), vec![
+			(-2.73483, -2.68258), (-2.63905, -2.56345), (-1.87479, -1.80172), (-1.01263, -0.72392), (-0.60043, -0.13262),
+			(0.13262, 0.60043), (0.72392, 1.01263), (1.80172, 1.87479), (2.56345, 2.63905), (2.68258, 2.73483),
+		], 1e-5));
+	}
+	
+	#[test]
+	fn triangular() {
+		let close = |a: Vec<(f64, f64)>, b: Vec<(f64, f64)>, eps: f64| a.len() == b.len() && a.into_iter().zip(b).all(|(x, y)| (x.0 - y.0).abs() < eps && (x.1 - y.1).abs() < eps);
+		assert!(close(f64::intervals(0, 1, Lattice::Triangular, &2.0, 0), vec![(-3.0, 6.0)], 1e-12));
+		assert!(close(f64::intervals(1, 1, Lattice::Triangular, &2.0, 0), vec![(-6.0, 3.0)], 1e-12));
+		let s3 = 3f64.sqrt();
+		assert!(close(f64::intervals(1, 2, Lattice::Triangular, &2.0, 0), vec![(-2.0 * s3, -s3), (s3, 2.0 * s3)], 1e-12));
+		let r = f64::intervals(1, 3, Lattice::Triangular, &2.0, 0);
+		assert!(close(r.clone(), vec![(-3.0, -2.22668), (-1.18479, 0.0), (3.0, 3.41147)], 1e-5));
+		let mirror = r.into_iter().rev().map(|(l, r)| (-r, -l)).collect();
+		assert!(close(f64::intervals(2, 3, Lattice::Triangular, &2.0, 0), mirror, 1e-12));
+		assert!(close(f64::intervals(4, 3, Lattice::Triangular, &2.0, 0), f64::intervals(2, 3, Lattice::Triangular, &2.0, 0), 1e-12));
+		assert!(close(f64::intervals(2, 5, Lattice::Triangular, &2.6, 0), vec![
+			(-3.30136, -3.08870), (-2.41654, -1.72067), (-1.44224, -0.88346), (2.78692, 3.04920), (3.41506, 3.60178),
+		], 1e-5));
+		assert!(std::panic::catch_unwind(|| f64::mirror_edges(Lattice::Triangular, vec![1.0])).is_err());
+	}
+	
+	#[test]
+	fn chern() {
+		assert_eq!(tknn(1, 3, 1), (1, 0));
+		assert_eq!(tknn(1, 3, 2), (-1, 1));
+		assert_eq!(tknn(2, 5, 1), (-2, 1));
+		assert_eq!(tknn(3, 8, 4), (4, -1));
+		assert_eq!(tknn(0, 1, 1), (0, 1));
+		let labels = |p, q, lattice, lambda| f64::gap_labels(p, q, lattice, &f64::intervals(p, q, lattice, &lambda, 0));
+		assert_eq!(labels(1, 4, Lattice::Square, 2.0), Some(vec![(1, 0), (2, 0), (-1, 1)]));
+		assert_eq!(labels(2, 5, Lattice::Square, 2.0), Some(vec![(-2, 1), (1, 0), (-1, 1), (2, 0)]));
+		assert_eq!(labels(1, 3, Lattice::Honeycomb, 2.0), Some(vec![(1, 0), (-1, 1), (1, 1), (-1, 2)]));
+		assert_eq!(labels(1, 1, Lattice::Honeycomb, 6.0), Some(vec![(0, 1)]));
+		assert_eq!(labels(1, 3, Lattice::Triangular, 2.0), Some(vec![(1, 0), (-1, 1)]));
+		assert_eq!(f64::gap_counts(4, Lattice::Square, &f64::intervals(1, 4, Lattice::Square, &2.0, 0)), Some(vec![1, 2, 3]));
+		assert_eq!(f64::gap_counts(4, Lattice::Square, &[(-2.0, -1.0), (-0.5, 0.5), (1.0, 2.0)]), Some(vec![1, 3]));
+		assert_eq!(f64::gap_counts(3, Lattice::Square, &[(-2.0, -1.0), (1.0, 2.0)]), None);
 	}
 	
 	#[test]
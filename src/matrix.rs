@@ -0,0 +1,129 @@
+use crate::{Zero, One};
+use std::ops;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<T> {
+	m: Vec<Vec<T>>,
+}
+
+impl<T> Matrix<T> {
+	pub fn new(m: Vec<Vec<T>>) -> Self {
+		assert!(m.iter().all(|row| row.len() == m.len()), "Matrix must be square");
+		Matrix {
+			m,
+		}
+	}
+
+	pub fn size(&self) -> usize {
+		self.m.len()
+	}
+}
+
+impl<T> Matrix<T> where T: Zero + Clone {
+	pub fn zero(n: usize) -> Self {
+		Matrix {
+			m: vec![vec![T::zero(); n]; n],
+		}
+	}
+}
+
+impl<T> Matrix<T> where T: Zero + One + Clone {
+	pub fn one(n: usize) -> Self {
+		let mut m = Self::zero(n);
+		for i in 0..n {
+			m.m[i][i] = T::one();
+		}
+		m
+	}
+}
+
+impl<T> Matrix<T> where T: Zero + Clone {
+	pub fn trace(&self) -> T {
+		(0..self.size()).fold(T::zero(), |a, i| a + self.m[i][i].clone())
+	}
+}
+
+impl<T> ops::Index<(usize, usize)> for Matrix<T> {
+	type Output = T;
+	fn index(&self, (i, j): (usize, usize)) -> &T {
+		&self.m[i][j]
+	}
+}
+
+impl<T> ops::Add for Matrix<T> where T: ops::Add<Output = T> {
+	type Output = Self;
+	fn add(self, rhs: Self) -> Self {
+		assert_eq!(self.size(), rhs.size());
+		Matrix {
+			m: self.m.into_iter().zip(rhs.m).map(|(l, r)| l.into_iter().zip(r).map(|(x, y)| x + y).collect()).collect(),
+		}
+	}
+}
+
+impl<'a, T> ops::Mul<&'a Matrix<T>> for &'a Matrix<T> where T: Zero + Clone + ops::Mul<Output = T> {
+	type Output = Matrix<T>;
+	fn mul(self, rhs: &'a Matrix<T>) -> Matrix<T> {
+		let n = self.size();
+		assert_eq!(n, rhs.size());
+		Matrix {
+			m: (0..n).map(|i| (0..n).map(|j| {
+				(0..n)
+					.filter(|&k| !self.m[i][k].is_zero() && !rhs.m[k][j].is_zero())
+					.fold(T::zero(), |a, k| a + self.m[i][k].clone() * rhs.m[k][j].clone())
+			}).collect()).collect(),
+		}
+	}
+}
+
+impl<T> ops::Mul for Matrix<T> where T: Zero + Clone + ops::Mul<Output = T> {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self {
+		&self * &rhs
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::matrix2x2;
+
+	#[test]
+	fn add() {
+		let m1 = Matrix::new(vec![
+			vec![2, 5, 1],
+			vec![-2, 4, 0],
+			vec![3, 3, 3]]);
+		let m2 = Matrix::new(vec![
+			vec![0, 7, 1],
+			vec![11, 8, 2],
+			vec![-3, 0, 1]]);
+		assert_eq!(m1 + m2, Matrix::new(vec![
+			vec![2, 12, 2],
+			vec![9, 12, 2],
+			vec![0, 3, 4]]));
+	}
+
+	#[test]
+	fn mul() {
+		let m1 = Matrix::new(vec![
+			vec![2, 5],
+			vec![-2, 4]]);
+		let m2 = Matrix::new(vec![
+			vec![0, 7],
+			vec![11, 8]]);
+		let m = m1.clone() * m2.clone();
+		let m22 = matrix2x2::Matrix::new(((2, 5), (-2, 4))) * matrix2x2::Matrix::new(((0, 7), (11, 8)));
+		assert_eq!(m22._into_tuple(), ((m[(0, 0)], m[(0, 1)]), (m[(1, 0)], m[(1, 1)])));
+		assert_eq!(m1.clone() * Matrix::one(2), m1);
+		assert_eq!(Matrix::one(2) * m1.clone(), m1);
+		let m3 = Matrix::new(vec![
+			vec![1, 2, 0],
+			vec![0, 1, 3],
+			vec![4, 0, 1]]);
+		assert_eq!(m3.clone() * m3.clone(), Matrix::new(vec![
+			vec![1, 4, 6],
+			vec![12, 1, 6],
+			vec![8, 8, 1]]));
+		assert_eq!((m3.clone() * m3).trace(), 3);
+	}
+}
@@ -30,6 +30,35 @@ impl<T> Matrix<T> where T: ops::Sub<Output = T> + ops::Mul<Output = T> {
 	}
 }
 
+impl<T> Matrix<T> where T: Clone + ops::Add<Output = T> {
+// the product with the entries multiplied by mul
+	pub fn mul_by<F>(&self, rhs: &Self, mul: &F) -> Self where F: Fn(&T, &T) -> T {
+		let (a, b) = (&self.m, &rhs.m);
+		Matrix {
+			m: (
+				(
+					mul(&(a.0).0, &(b.0).0) + mul(&(a.0).1, &(b.1).0),
+					mul(&(a.0).0, &(b.0).1) + mul(&(a.0).1, &(b.1).1),
+				),
+				(
+					mul(&(a.1).0, &(b.0).0) + mul(&(a.1).1, &(b.1).0),
+					mul(&(a.1).0, &(b.0).1) + mul(&(a.1).1, &(b.1).1),
+				)
+			),
+		}
+	}
+	
+// m_n ⋯ m_2 m_1 by a balanced tree of products, whose two factors are always of about the same size
+	pub fn product_by<F>(ms: &[Self], mul: &F) -> Self where F: Fn(&T, &T) -> T {
+		assert!(!ms.is_empty());
+		if ms.len() == 1 {
+			return ms[0].clone()
+		}
+		let (low, high) = ms.split_at(ms.len() / 2);
+		Self::product_by(high, mul).mul_by(&Self::product_by(low, mul), mul)
+	}
+}
+
 impl<T> ops::Add for Matrix<T> where T: ops::Add<Output = T> {
 	type Output = Self;
 	fn add(self, rhs: Self) -> Self {
@@ -149,6 +178,15 @@ mod tests {
 		assert_eq!(Matrix::one() * m1, m1);
 	}
 	
+	#[test]
+	fn product() {
+		let ms = (0..7).map(|i| Matrix::new((
+		(i, 1),
+		(-1, 2 - i)))).collect::<Vec<_>>();
+		let seq = ms.iter().fold(Matrix::one(), |a, m| *m * a);
+		assert_eq!(Matrix::product_by(&ms, &|a: &i64, b: &i64| a * b), seq);
+	}
+	
 	#[test]
 	fn det() {
 		assert_eq!(Matrix::new((
@@ -1,4 +1,78 @@
-pub const DEC: &[u8] = b"3.1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679821480865132823066470938446095505822317253594081284811174502841027019385211055596446229489549303819644288109756659334461284756482337867831652712019091456485669234603486104543266482133936072602491412737245870066063155881748815209209628292540917153643678925903600113305305488204665213841469519415116094330572703657595919530921861173819326117931051185480744623799627495673518857527248912279381830119491298336733624406566430860213949463952247371907021798609437027705392171762931767523846748184676694051320005681271452635608277857713427577896091736371787214684409012249534301465495853710507922796892589235420199561121290219608640344181598136297747713099605187072113499999983729780499510597317328160963185950244594553469083026425223082533446850352619311881710100031378387528865875332083814206171776691473035982534904287554687311595628638823537875937519577818577805321712268066130019278766111959092164201989";
+use num_bigint::BigInt;
+use std::sync::Mutex;
+
+// the longest value computed so far: (n, floor(π·10^n))
+static CACHE: Mutex<Option<(usize, BigInt)>> = Mutex::new(None);
+
+// floor(π·10^n), from the cache if it holds at least n digits
+pub fn floor(n: usize) -> BigInt {
+	let mut cache = CACHE.lock().unwrap();
+	if let Some((m, pi)) = &*cache {
+		if *m >= n {
+			return pi / pow10(m - n)
+		}
+	}
+	let pi = machin(n);
+	*cache = Some((n, pi.clone()));
+	pi
+}
+
+fn pow10(n: usize) -> BigInt {
+	(0..n).fold(BigInt::from(1), |a, _| a * 10)
+}
+
+// floor(arctan(1/x)·10^n) up to less than the returned number of units: every term is floor(10^n/x^(2k+1))/(2k+1),
+// exactly rounded down since nested floor divisions by integers are a single one
+fn arctan_inv(x: u32, n: usize) -> (BigInt, u32) {
+	let x2 = BigInt::from(x) * x;
+	let mut power = pow10(n) / x;
+	let mut sum = BigInt::from(0);
+	let mut k = 0u32;
+	while power > BigInt::from(0) {
+		let term = power.clone() / (2*k + 1);
+		if k.is_multiple_of(2) {
+			sum += term;
+		} else {
+			sum -= term;
+		}
+		power /= &x2;
+		k += 1;
+	}
+	(sum, k + 1)
+}
+
+// Machin's formula π = 16arctan(1/5) - 4arctan(1/239) with guard digits,
+// added until the error bound of the sum cannot change the floor
+fn machin(n: usize) -> BigInt {
+	let mut guard = 10;
+	loop {
+		let (a, ka) = arctan_inv(5, n + guard);
+		let (b, kb) = arctan_inv(239, n + guard);
+		let pi = a * 16u32 - b * 4u32;
+		let err = 16 * ka + 4 * kb;
+		let scale = pow10(guard);
+		let (lo, hi) = ((pi.clone() - err) / &scale, (pi + err) / &scale);
+		if lo == hi {
+			return lo
+		}
+		guard += 5;
+	}
+}
 
 // http://www.befria.nu/elias/pi/binpi.html
 pub const _BIN: &[u8] = b"11.00100100001111110110101010001000100001011010001100001000110100110001001100011001100010100010111000000011011100000111001101000100101001000000100100111000001000100010100110011111001100011101000000001000001011101111101010011000111011000100111001101100100010010100010100101000001000011110011000111000110100000001001101110111101111100101010001100110110011110011010011101001000011000110110011000000101011000010100110110111110010010111110001010000110111010011111110000100110101011011010110110101010001110000100100010111100100100001011011010101110110011000100101111001111110110001101111010001001100010000101110100110100110001101111110110101101011000010111111111101011100101101101111010000000110101101111110110111101110001110000110101111111011010110101000100110011111101001011010111010011111001001000001000101111100010010110001111111100110010010010010100001100110010100011110110011100100010110110011110111000010000000000111110010111000101000010110001110111111000001011001100011011010010010000011011000011100010101011101001110011010011010010001011000111111101010001111110100100100110011110101111110000011011001010101110100100011110111001010001110101101100101100001110001100010111100110101011000100000100001010101001010111011100111101101010100101001000001110111000010010110100101100110110101100111000011000011010101001110010010101011110010011000000001001111000101110100011011000000100011001010000110000010000101111100001100101001000001011110010001100010111000110110110011100011101111100011100111100111011100101100000110000000111010000110000000111001101100100111100000111010001011101100000001111010001010001111101101011100010101011101111100000110111101001100010100101100100111011110001010111100101111110110100101010101100000010111000110000011100110010101010010010111110011101010100101010110101011100101000101011101001000100110000110001001100011111010000001010001000000010101011100101000111001011010100010101010101011000100001011011010110100110011000101110000110100000100010100000111101000110011101010000101010100100001101010111101111100011100101110100110010011101100111110111000010100000100010110001101101111101111000010101000101011101010011100010101011101011101000001100000110001111101101100111001011100001111100001011010011011100001111001001100011110101011111101011010111010001100110110110000100100110011110101110001111010001100100101001110000001001010001001010110000110011101110011101110001111010010001001100001101011010010111011100110101111110001001011111111101000000110110110011000101000001000011001001101100001110110000000100111001100111110110010000110101001100100010100100001111100101011000110000001011101111011001000000000110010111011111000010001011101010111011110100110000101011101011011000111011100001001100010001100000010111010110110010100011011100010000010001110001001001111101000000111010011100101101010110011000101000011110110110101101111111100111000001111110100010000100011100100101110000010110100010010000010101001001000010000100000000001000110100111001000111100000100101010011110000111111001101101011110001000011100011001101000010000101111011011101001011011001001101001100111000011001001110001100001101010111101001110001000111100000110101001010001101000001101001011011000010101000010111101101000100101100000111110100111001010001010101101010001001100111010001101101110111011110000101101101100000100110111101000111011111001001011101000111011111100000101000001111110111110110010101010011000101000011111000101100101000111010011100110101111000000010111011001100110110010100101100100111110100000100100001100001110100010001000110011101110100001100001100101000101011011111001111110110100011111011000010010100101110000110011101110001011010111101011111011100000011011110111010111011000100001011100000100100000011100110100000000011010010001001001111101010110110000010110101010100110010011101101001110101010011000100011011000111111011101110000011000011011111111101101111101110010010000101001101100000010001111010011011111010000110101110010010011010000000010100001001001001000110110110000111111101010110100110100100111110001110000001001101100000111010100110111001011001001100000001001100100011011011110110010010111010100011110011101100011110110111010001101111011110111111000111111111001010000000110101011011001111001010011000011101110010111011011001110000010111101000001001100000000000110101110101100000110101001010011111011011001000000100111110110000011000100010111100101110010011110110000100001100101101010001001000110001101101000111110110110111110101111001111100110110001010011101101010001001100111001101100101110101100111011010100101110110001101111011011011111110001010001000111111001101100110000100101010010110011001100100000010100010101000100101011110101111010111101000010011011111011100011110100000000010011011110001100110100101011111101011001100000111100101000000001110001100100101110010010111011001111000000110010111010100001010111010001011100100001110100000011111101001000001011010111110011100110111001110100111111101111011011010101010111100111000000101111010001101001100000001100100000101011010110101000010000000011000110010000000010110001110010011110010110011110011111001001011111111011111011000111111010001111001100100011101010010111101001111110001101101100110010001000101111100000111100011101010001011011011111111111010110000101101011000101010010111101010000000111101100100010101101000001010101001010101011001100100011110110110101111110101111110100100011100001110110000001010011001100010111101101001000001111100000000011011111100000101001111001011100010101111011101111001010011011111000110010100000000110101000011101010110001011101101111100010111011010011101101111010101010000101010100011110110001010000111111011111111110000111010110001100111001100101100011010001100010011110101010101110011011010010101101100100111101100001011101111001010010110001100100011100001111111111010001101011101101110001111000000010001101000000001000011111010001111011001100011111101001000011000001110111000010010101111110010110101011011000010110111010001110100110101101110011010010100111110010001111001101101101111100001000101011001011101001010001110010010011011110001001011111110111001011110010000111000011101110111110010110110101010010011001011011111100011001101100010111110110001001101000001110011101110010011000110111010001110111100100000110010101101101000110110011101110100110000000001110100000111111010011110111111100010101111110001000111111011010010010101110110111101101001001101101011101001000010010001100110001110101010101101100011100111000101101011100100111101010110100000110100001000111011010001110100001010111111000111001001011110000010001110001111000101101100101111100011100111010110010100101101111000111111110110111000101111101111110010000100100010101101100100100010001000100010111000000100101001000000001101111100000001110001001111101011010101111010100000011010001000111111000011000111001101000111001111111100011001000110110011101010001100000110101101001011110010111100100010000110001011111000001110000101110111011111101010011101010010110111111110100010110000001000011111101000011110010110100000110011000000111110110101011011110111010011101000000110001010110011110011110101101100111010001001111000101001100110110100101010000100111111100000111111010001001111100000101101110111110011000100001110111000000111010010101011011010100011011001000101100101111110100010011001101000000010010101011101110000010110010011110011000111001100010100001000010001101000010100011101111110011010101101001000000110010101110111101101011111101010000110110001110101010001000010111101011111101110011101001101011100111111101011110011011010111100001100011110110011111010001001101000001101011001000001000110111101001110101110000111100111111001001001000000000010010100001110001011010010000001110001101100110101111000100010011010000000000010111011010101111011100011100000101011110010010001100100001101101001101111110000000010011011100100011110010101010110001110010001000111010101100111011111101001101010101001111000110000010100001110001001110110010101101001010011011111110010000001111101010110111010001000000010111001011011100111000101100000110010011000000011011101100110001010010101110011111010100100010001110010000001100101101000010011100111001101001010010000011011001101000111001011011100101001111011000101001010100101001010000110110101000100000000010100101001101001010011001010010001010111010110000011110101011100111111101111001001101111000110111001000010101101100000101001000111011010000001111001100111010000000000000010001011101001101111101101010101011100011011111010010001111111110010100101101110110001101011001010100000110111011001000101011011011001100011011001010010000111100111101110011111100110110110111111110011010000000101001011101100010110000101010101100110010001010011101100000010110101011101101010011001111110001111101000010000100010111010010001111001100101101110100001010000011101101010010010110111101001110000111010011011010110110011001010010100010011011011011101010000100100101110110001000001100100100110001000111010110101101110101001101011000001001001101001111101111101111101100111001110111001100000101110001000111111101101101100100110011011101100101010101000110001110001011010011001101000010111111111110101011001100100010100100110110011000010101100011001111011100001000110010011011000000010101001010111010100001001010011000010100110100000010110010001001101000000111001000001100000111010001111100011111101010100100110001001101001011011010000101001110101100101011010111000111111100100110101101001100111110111001111111101011010100001110100101001110000000111111011111110100000110000111101010100110100101101001110001110011011110000001001010101110111000001010011001101110100100000100001101000010001110000111010110010011001100011100000101110100111000110000000100001111011001100010111100000100101101000011010110011111100111110101110101110111111001001001111001001011100011000000101000110101101101010011100001010000101101000011111110011010110000100010100101010000011100010100001101011011110011100010100110000010110101010010100000000011100110111001111100000011110000100000111000111111111011110101011100101110010001110011111010100010011101100010101110001011011110010101110001011000000111010110110100011011111110000010100000000110000001101111100000001110000011111000001000000001000000000101100111111111110101110000011001111010100011010001111001011010101110100101100100010010110000011011110100101100011011100000010010010000110111101110100011001000100010011111110010111110010101001001011111111011010010100001100100100011101110011001000101111010101000111000000010011101011100101111001011000000100110111110000101101101011011100110010001011010101110110001101001001101011110011110111011010011110101001010001000110000101000110000011111101000000000011000011101110110011001000110001110011111010100100011101010001111001000001111000100011100011001101100110010011101111101010000011100010111100110010100000001011101110100001000110000011111010110011001100010100111001010100100010110011100001001111011011011011100100001000011011110100001000001101000000111111011000001010000001001011111100101100101110000001001010010000001001001001011101111100011110010101011001111001101100000111001010111100101011111000100110101111110111101001101001110111000111111101100110010011000010000001000010110011100010111010111000010010110111001100111100111111001011100101010100010010011100100001111100101110011010110111000100100100010100000001101011011101111001101001111110000100110011011000011101111010010110000100011100011000011101000000100011011010000101111011110010011111100110101011110011101001010010110111110110001100111011000111101011101100001110101101101110000101000111011111101001100011000010010100001101100110110001000110010011000011110100101110111100011100000110000100011100110010000101011101100100001000110111010100001100111011001101110010010011000010101110100001011000010010101000010100110101000011001010100110010111000100010100010101000010010100000000000000001000010011001110101110010011011101011100011101111111111000100111100001000000110001010011100101010110000001101011000111011111010110010111110001000100011001100110110000010000110101010101101111000111010111101000111100011101101011001111000001000100011000001110110101100100100100101001010000100111110010100011111110011011101101100101111111000111111011111110101001111010111010101111110010110000011110000101010011110001101110100001101110001101000101011100001110101011101001011011111011000110000110000011100101111000001010010110100011111000101010101100110111011100011111111001110001110001001110001111010000011011111010001010010110010111011100101110011001100111100111000111010000111110000000001111101000100111010110010100100110011011001000001001010010111001001100110010010111100010011100000100001011001101101010110001100001010100001110101110101001010011100010111010100111100010100101111111000011110001010011000111100000101000101101111101001111001011110111010011101010011100110110000111010010101100111101000110010011100100100110000011110001100111000010011110010110000001010010001000111010011100001000111101110001001100010010101101101110101110101101111111100110111011101010110000110001111101100110111000111011110001000101100101011010011001111011110010001000001110110001011111110011011111010001000000011000110011111111001010001100001100110010110111011110111110111110011011000101101010100101011001010101100000100001100001010110100010101011100110000000001011101110110011101010010100001111110110110010111110010101001110110010101011101111011111011010110101011011011011100010111110000100000101010010000110110110001010000010100100000111011000010111000011101100110111010100011101110101011000011001111100010101000100000001001111001100101010000011000011101011011000011011110110010110000000110011010011111110000111101010101000000011011000111100111110110101011100110101110010010000010011000111000010100010001110011101010110011110100111100000101111001011101010101101111000010100111011101100110010000110101111000110000001100010001011001010011110011100101010110101110010101011101100101111001110000100011011100110010010001011000111101010111100011001101111011111000011001010101000000010001101101001101110010110010101011010101110110101000001000000011010000101101000110010001111000010101010110100101100110011000110011110111010011101010111000000001000011011100011110111100110110101010000001011000110011000011101011111101000001001100110010101111101111001100101111110011000100011110101111101101010001111100000110111100010001001101010010111111000110010110101110111000100011110110110010011010111110001011001101000000100101000000100001110001101011000100000101001110001111110011000011111110101101001011011011110110111111010000101111000010110001011101010011001010101111111010110000100101001010001101100100010011100100110001110011011100000111100001111111111000110101100001001000110100101101100110110110011000010101110101101010011001011100011000001010100100011111101100101001000111001000110110110111100001100010010100001011000111010111111001011101111001101001100011011111111111010101111111000101000111011010110000111101110011111000011110001110011010111010100101000010100110110011110100001100100101101111110001101000010000100000101110100010100001000000011111000010011111000000100010111101110111000101011011010100011101010101010101111101010110110110110110001001111000101011111101011001011010011111101000011000111010000101111010001000010111011110110101010111011101101010110010101001111001110110001110101000001110011010010000100000101110110000001111001111001100111101000011010000101010011011100011111100100010010110100011101101010001111011000000101100010010100001100111101100010101000011111001001011011100011010010011001000110111111001000100010000011101000001100000111000111101101101010001101111111000101010010010011000011011010011100101101110100100100100100011110000100100010100000101101010110100100101011001010000101000010010101101110111111000000001010110100011001010010001001110100010100011000101011000101110100001000111000001000001110000000000101100001000010100011010010101000001100010101011111010111101010000111011010110111110100001111100010001100111111011100000110000100110011011100101111000010010010100011011001001101111110010000011101011001011111111011001111000101101100001000100011101111011011011111001101111000110111010110011100101111101110011101000110000001000000100001011111001010100111110011100111011100110010011011101010011000000111100000001000010000011001111110000101000010011110111010001110111111011000010101010110000111011001100101110011010110101001011010011010011110101010110001010000110000000110110000100101101000000100101010111111110010000000000010111100101011011100100111100100010001111010001011101100001101000101001101001000010011111101110101010110011100000101000011100001111010011110110010011101101101110011110110111101001100010000010101011000100011001101011001110101111111011010011110011110001101100111010000110100000011000101110001000011010001100101011100010011111000111000110110000011110100101000111110001001111011110001011011011111111100100000000101010011111000100001111001111000111110110000001111010100101011100110111000111001111100101011110110111000001110101101111101111110100100111101010110100110100010010100100000010100000011110111111101100100110000100110000111001001010001101001001010010011010001000001000101010010000011110111011101100000001011010100111101111011110011110100011010110010111011010100101000100000000001101000110101000000100000100100011100010011001100100000111101000110101001000011101101111101010010110111010100000000000001100001101011110001111000111001111101100010111010010111001001000100010101000110000101000010000101001111011101001011111110001011100010000100000001001101100101011111110000011101100101101011010110010001101011110111000011110100110111011101001101100110101000000010111101000101101111111011110000001001111011000000001110111101100101111000010101111111101011000110110111010000001100011100101110000101000001001001011011101011001001111011001101010101111111010011100101000001110110100010010101000111111001101010101111001010000010101001101000101000010100000111100000100101010100110000010000101001111101000000101000101100100001101101101011101001101101100110110111111011011010001101110000010100011000101101011101001000011010010000000001101000000011101100000010100100001001111010000110001101111011100100111100111111111111101010001011101000100001111010110110001100101101011000110011100000000001100111101011110100110101101011011010101010110011100001111001111100110100110011011101011111111011001100111001111000101000111001100101000000011010110010101001000010001000001111111010011110001101011101100111110011100001011011100111101110001110011101011110101011001110110001001001001110100010110001110111001001111110101111011101001011011011010001100001010110001001101010001101100110001100011110101011100011100101111011001000111010011011101111101001110100110111010101101101000011001100100110100001000001111001111111011111001010011110000010000011111011111110110000101011110101010011101101100011111110101100111001011101000101010000000101011010101100101110100100100010010101001001110101010101010011001110100011101000100000100000111000110110000111111111100110101110101001101101111101000010010110100101010100101101010101101010000110011110111100101000010001010110011010010110001100110010101001001010010110001110011001111000011101101100110011101001100010101001001010010101100011111100110001001001011111100101011110111101000111111000011100100100000010100100110001011111001111110111111000111010000000001000000100001001110010111101110000100000001011101100010101010111000000010100101000001011001110001110010101110000010001010101001000111001001100011001101101001000100100100011000001000100110011111111000111000011111000011011011100000001111111100111001001111011100100000100000100000111110000111101000000010001110111100110100100010111011000100001101110000101110011001001011111010100011110101111010101100110111100000011010001111100101011110011000001100011110100000100010001001101010110010000100101011110110111100000110100011000000010101010011100011000001101111111111000111010001010001100011111011000110110110000011011000011100001001010110100110000100000001011100001001100101001111010101111011001100100111111010001110010101101000110000001000101010110101100100011100101011110000000110011001111101001001011100001001110110010010000001011011000101110111010111110101110010010001010000101101100101010001000001110111001101011101000001101100110011101111001110010000011001000110000101101101000101111011100101000110100000001001001111000010001011001010110110111100101001111110101100100011111010000100001100010111001111100110011110101111100000101010001001001101000110110111110000111011111010100100011111010110000111001110111111101001001111111001100111110100011010001111000001010010001110110001101000001100110010010111011111111011101000011101001101111011011101010101111110100111110001111110100110111101010000001001011011100011000001010000111101011110111011111100010011001000110111110000101001100110110110110111001101011000011011100011001111011010101010001000001101101011001110010110000110111001001110110010111010100001110111101110011010000111010000000010011110001001010010000110111000111110011000000000011111111101000111011010100111001000011111001001001101001000011111110110100001011011001100111101110011111111110111100111011011011011111011001110010100000100100011100111100001011110110010001010101011110101000111011101100010011001011111000100001010001010110111010110100100100011110111001010001111001101111110111011000111011110101101110101100110111001110010010111010110011110011000001000101011001011110011000000000100110111000101001011111110100001011100011000100101101011010000100001010101101101001111100011001101010001010110011101100010010011101010100110011001100011110000010111011110001000111000110101000010010010000100011011110110111100100100101000111100111000001101010000110111011111001100100101111111011011000110101000000011010011010110001000000011000000100011100101011101101111110100011110100100101101111011101100011100010111000011100001111001001010001000100001000010110010110010000101000010010000100111000011011011001000011001110110001101110110101011010101111101010001010100110010010101111011001110100111011011010100001101010100001011111101111101011111111101001100010000110010011100100110000111111111010011101101111001000000001010111111100001111011111000000100001100110000001111000011110111111100001100000000000110110000001001101110100011111110110000011010001101111011000111000000111111011000001110111010001011010111000000100110101110011011011111100110011001000001101000010011010110011001111110000000111101010101101110001101100001000000001000001100001110011110000000000010111100101111101110111101000000101011110111110101111011110100010101110001001000101010101000110010000101001100110111111010110000010111001100001010011100101100011110100100011111111001011011101111111011010001011110100011101001110111100111000100001111000100110111101110000100101001101100110111110011100001111001000101100111000111001110100101101000111010111110010010101010100011011111100110110011011100101111010111010110010011001100001100010110001110111011111100001001000010001101010000011100111100110010001010111111001010111100010010001100110111001011001100011100010000010110100010101110111000010001100110101010101010110010001110010010000001011011110010011001011100100001011101011001110000110111011100000100000010111010000000100011010100001100010010010000111010101110100101010011001111010110111011111110001100110110110111000001010100111011100000010010110011000101101000010011010000111000100001100100100011000110011111010000101101000011111000000100000100111110000101111101000110001001010100110011010000000100101000111010110111011111110000100000001101010111001001111010001110100001011101001011010010011011111101000011000011011110010000011110010100001101000111100010110100111011100101101111101101010000011010101110011100100000110111111101010000111100010110011101001101101001111110011010111111101010010010100000001000101011110000000011010011100000110100000111111101010100000000000101011010111000100000011011110011011010000001001111001101011111000100011000010011101110111001111111000011001000001110000110110000001001100000001100110000110101000000001101011010111110000000101110111101000101000110000001111010110000110111000000000000001100000010110001010101000110000110111000111110101100010000100011110011010011110110101110010001100111000111010100110001101010011110000101101110110010100110000101100001000010110001101001011101111001011111011100101011010010000101111001011011011011110111010111111110001111101101000011100111001011001000111010111011001101111000001011110010000001001010010110111110000000001100010000011100101110010000010100011110101111100100100100111110000100100100001101110001101110010010111110111001001001101100111011011100100011010110000010101101110110100110100111001111010111000111111001110110101010100010101010111100000001000111111001010010110110101110110000011110101111100110100110100110110101101000011111100010000011110010100001110111101011110101100010110000111100110111110001010001010000101000101001101100101101100010100010001001100111100011011111101010111000111111001110101011011100001010011101100010000110110001010101011111111001110110111011100011011001000001101111101011110011010001100100011010010010010011000111000001000010010011001110000111011111010100011100100000001100000000000001110000000111010001110011100111000110111110100111111101011110101110011111010101111000010011101110011011101011010110001010010110100011011010111001011000001100111100111100100111110100011001101110100001011010011100000100010011101000000100110011011110010011011101111101101010100010001100011101001110110111111000011110111001100010101110101100010110100011100011111101100011100000000110001000111101110110111100011000001101101101011001000011010000110010000010001011011001001101110101100011011111001101010001101100110111010110100010101110100100010101011001011111011110010010100011011100111100111000110101000110111011011010010011001010100100111000010110010000101001100001111111110001110111001000110100011011101111001111101110101010111001100001010000111010100110011010000010011011100011000101001001110011011101111011011101010011011101001000110010100001010110010010101001001101110100010111110010111101110001100000100101000011111101011010101111100000110101000101101010100011001101001100011111011111000110011100010100110101000011011101110001000101100000010001001110000101011100001000011001001000010111011110110101001010001111000000011101010101001110011110010110100001010010010000011110000000110000110111010100110111110100101101010010011011000111111100101000101010101000010111010011001000101101111010110001010000010011010100010111110011010011100111010001110101110000101001011101010011001010110000110111011110101010101100010111010011100011100101111111011111101001111110111010100101111011111011010001111110000010001101111011010010111011111111010000010100101100110000000111001001010100100010101100001111011000010000110000000011001101100001001111001101010110100111011001111101110010110010011111010011001000011111101010110101001111000110100110101111001011100101100111100001011011111011001000000100010101110001011010100011001011011010101101011000011101000000001011111011010011001111101110100011100111100111110110101100111110001111101001011010010100000011111100111110010010111001111101011011111001010111000100110110101101011010110101101000111001001011010100010001111010101001100111000000010100110101100011100011110000000011001101001011110011001000111101100001010110011111101111011011001001111111010100110111110100011010011110001001000110100101000001110110101011111001100111110001101010101100110001010010111100100010011001011100010100001111000010111110000000110010001111011010111010101100000010101011111011110010110000011100100010011100011110100110101111010001100000101010000010101101101110101001000100011110100011011011011101000000011101000010110000100100101000001010110010011110000101111011100001111101011100111100001010100111100100100000101011110100010100101110010011100011010111011001010100100111010000001110010101000011011001111110110110110011011000111100110001100100001111101011111010110011100011001101111101100100110110111001111001100011001011101010011001111011001001010001011000101010101111111011111010100000011010101100011010010000010100010101011101000111100101110110010100001010001011101110001000111000010000010101101100111111000101010111100110001010001011001111100110010101101100100100101111101001101111010000001011101010001001110000011000011011100100011100011011110011101010110000110001010010011001000001111100110010001111010100111101010010000110000101111101100111110011110111100111001010001001000011100111001100100011101110100111110111110001100101010100010110110111000110111111011000011001010010011110101000110010010001101111001010011011010010110010000010011111001101000000010100010101011100000100000010000110111010110110110110010001001000110100110000101001011011111110100001001000001110010000101100110101100111001101001000110000010100110010001000101110000001101110101011000011011001101111011001111000111000010000011001000101011100101101110111110111101111101110100011011010110001000110101000000110011001101001000000001011111110110101110110100111000111011101111011101101000100110101001111110001110100101100111111111010001010011111000110101000010100100010010111100101101001100110111010101011100101110101011001110101010001111101001100100100001001011101110001101011001100001001010101110101111110011110001101111010001111101001010011011111001000110001101010100001011110101110110011110101011101100001001110111000110111111011001001110011000110111000001110100000011100000110110001101111001110101101100010011010101111111100001110010000101100111000110101111010100110111110101011101010000000100000011001011000010000100111010110100111000101100110000110100110100100100011001101010000000010001010110101111100001001110000110110000000001000010100010010101100110000011101000011101000001101011100010011111101101001100111001101110101000000100100001101111001111110011101110000010001101010010000010101011100000100000000100011010000111010100101100100111011100100010011111111000011000010001010101100000101100011110011110010011001111111101110010111011001110100111100100101011001101000100010100100101101111011010000010001000001110011110000101010001110011100111100101001011001011110011001011001001101101111010000000011111101110101100100111100000000111001100100001111110101111001100011111010001111101101100111100000001000100011100001110100001111010001010101011000111000110101001000010000111010010011101010001001111101111011001101011010000110110101101111011001011110101010000101011011010001110000000001100111001110000110010101011000110100100010011011001100111100011011111100100110001011111001110000010110001001010110100111111110111100111100101100110110111010000111111010110111011001110101111001011010101000110011111111100100111110110010100010110011100101111111001011100100010001011000001010111100110111111000010101000001111100100011111110001110001100110111001010000010101001001011111101011100101100100110110000111001110101101101001110011101011110000101010100001100100010110010001001010111010101010001101000110110110110000010000011101011110111000110000010101101010000011000001000011010010010100000110010111001011000000111010010001000010111000001110110001101110000011100001011010011000110110110011101101001100100110001010000010111110001100100111100011101001011001001001111100011111100101010011001011100000110100111001001011011111110100111010000000110100001010111000100101110001111100100001111000011011000010100111010001000001010010111010001100110100100011001100010110111110011100010010000011000011011101100011001011011000110111110011010110011111100011011001101110011001001011110010111011100110000010110110111101000111000011111110001111110001000111011110010101001100110110100101010000011110110110101101100010010001110011100110001001111001110011111100110100111110011111100110111100010110000110001011000101100110111111010010110000011101000001011000010010001111110100101100010111110110111110110010001010011001111101010010001111110011010101111010011000110010011101100010001110010011101010000011010100110001010101101100110011001101000000101010110011110000100000010110001001011010011101011110101110110101011011100001011000110110100101111000100011010010011100111100110011011110100101100110001010010010100000011011100101001001110100000100110001010000100100000001101101110001110001100101011000010100111001101100011011000111101111010011001001111010000101000000101001000101111000011101000000000110110000111111001001111011100110101100100110101010010100111111110101100010101010000000111100000000101110110010010110111111111000100011010110111101110100101111011001110001000100100110100100000101101100100000010000000010001000101011011011001011110011110111110011001101011101101001110000101011010100110001000100111110110000000001011001000000111000111101001100111000101010111011110101100000001001010100011110101101111100001011101000111000001000001001110011110111010001101100111001110110";
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn digits() {
+		assert_eq!(floor(0), BigInt::from(3));
+		assert_eq!(floor(50).to_string(), "314159265358979323846264338327950288419716939937510");
+		let long = floor(1200);
+		assert_eq!(long.to_string().len(), 1201);
+		assert_eq!(floor(999), long / pow10(201));
+		assert_eq!(floor(12).to_string(), "3141592653589");
+	}
+}
@@ -11,6 +11,7 @@ impl<T> Polynomial<T> {
 		self.factors
 	}
 	
+	#[allow(clippy::should_implement_trait)]
 	pub fn as_ref(&self) -> &Vec<T> {
 		&self.factors
 	}
@@ -153,7 +154,7 @@ impl<T> ops::Add for Polynomial<T> where T: Zero + Clone {
 	fn add(mut self, mut rhs: Self) -> Self {
 		self.extend(rhs.factors.len());
 		rhs.extend(self.factors.len());
-		let v: Vec<T> = self.factors.into_iter().zip(rhs.factors.into_iter()).map(|(l, r)| l + r).collect();
+		let v: Vec<T> = self.factors.into_iter().zip(rhs.factors).map(|(l, r)| l + r).collect();
 		Self::from(v).cleanup()
 	}
 }
@@ -163,7 +164,7 @@ impl<T> ops::Sub for Polynomial<T> where T: Zero + ops::Sub<Output=T> + Clone {
 	fn sub(mut self, mut rhs: Self) -> Self {
 		self.extend(rhs.factors.len());
 		rhs.extend(self.factors.len());
-		let v: Vec<T> = self.factors.into_iter().zip(rhs.factors.into_iter()).map(|(l, r)| l - r).collect();
+		let v: Vec<T> = self.factors.into_iter().zip(rhs.factors).map(|(l, r)| l - r).collect();
 		Self::from(v).cleanup()
 	}
 }
@@ -200,6 +201,7 @@ impl<T> ops::Mul<T> for Polynomial<T> where T: Zero + ops::Mul<Output=T> + Clone
 
 impl<T> ops::Mul for Polynomial<T> where T: Zero + ops::Mul<Output=T> + Clone {
 	type Output = Polynomial<T>;
+	#[allow(clippy::suspicious_arithmetic_impl)]
 	fn mul(self, rhs: Self) -> Self {
 		let v: Vec<T> = (0..=(self.degree() + rhs.degree())).map(|n| {
 			let b = if n > rhs.degree() { n - rhs.degree() } else {0};
@@ -210,6 +212,58 @@ impl<T> ops::Mul for Polynomial<T> where T: Zero + ops::Mul<Output=T> + Clone {
 	}
 }
 
+// below this length, factors are multiplied by the schoolbook method
+const KARATSUBA_CUTOFF: usize = 16;
+
+fn add_into<T>(r: &mut [T], a: &[T]) where T: Zero + Clone {
+	for (x, y) in r.iter_mut().zip(a) {
+		*x = x.clone() + y.clone();
+	}
+}
+
+fn sub_into<T>(r: &mut [T], a: &[T]) where T: Zero + ops::Sub<Output=T> + Clone {
+	for (x, y) in r.iter_mut().zip(a) {
+		*x = x.clone() - y.clone();
+	}
+}
+
+// a·b by Karatsuba's splitting a = a0 + x^m a1, b = b0 + x^m b1, with a1b0 + a0b1 = (a0 + a1)(b0 + b1) - a0b0 - a1b1
+fn karatsuba<T>(a: &[T], b: &[T]) -> Vec<T> where T: Zero + ops::Sub<Output=T> + ops::Mul<Output=T> + Clone {
+	if a.len().min(b.len()) < KARATSUBA_CUTOFF {
+		let mut r = vec![T::zero(); a.len() + b.len() - 1];
+		for (i, x) in a.iter().enumerate() {
+			for (j, y) in b.iter().enumerate() {
+				r[i + j] = r[i + j].clone() + x.clone() * y.clone();
+			}
+		}
+		return r
+	}
+	let m = a.len().min(b.len()) / 2;
+	let sum = |c: &[T]| {
+		let mut s = c[m..].to_vec();
+		add_into(&mut s, &c[..m]);
+		s
+	};
+	let low = karatsuba(&a[..m], &b[..m]);
+	let high = karatsuba(&a[m..], &b[m..]);
+	let mut mid = karatsuba(&sum(a), &sum(b));
+	sub_into(&mut mid, &low);
+	sub_into(&mut mid, &high);
+	let mut r = vec![T::zero(); a.len() + b.len() - 1];
+	add_into(&mut r, &low);
+	add_into(&mut r[m..], &mid);
+	add_into(&mut r[2*m..], &high);
+	r
+}
+
+impl<T> Polynomial<T> where T: Zero + ops::Sub<Output=T> + ops::Mul<Output=T> + Clone {
+// the product by Karatsuba's method, O(n^1.59) instead of O(n^2) multiplications of coefficients;
+// the subtractions make it unsuitable for interval coefficients, which they widen
+	pub fn karatsuba(&self, rhs: &Self) -> Self {
+		Self::from(karatsuba(&self.factors, &rhs.factors))
+	}
+}
+
 impl<T> ops::Rem for Polynomial<T> where T: Zero + ops::Sub<Output=T> + ops::Mul<Output=T> + ops::Div<Output=T> + Clone {
 	type Output = Polynomial<T>;
 	fn rem(mut self, rhs: Self) -> Self {
@@ -230,17 +284,23 @@ impl<T> ops::Rem for Polynomial<T> where T: Zero + ops::Sub<Output=T> + ops::Mul
 	}
 }
 
-impl<T> Polynomial<T> where T: Zero + One + ops::Sub<Output=T> + ops::Div<Output=T> + Clone {
-	fn _gcd(&self, rhs: &Self) -> Self {
-		let mut a = self.clone();
-		let mut b = rhs.clone();
-		while !b.is_zero() {
-			let t = a % b.clone();
-			a = b;
-			b = t;
+impl<T> Polynomial<T> where T: Zero + ops::Sub<Output=T> + ops::Mul<Output=T> + ops::Div<Output=T> + Clone {
+// the quotient of the long division, the remainder is dropped
+	pub fn quotient(&self, rhs: &Self) -> Self {
+		let n = rhs.degree();
+		if self.degree() < n {
+			return Self::zero()
 		}
-		let f = T::one() / a.factors.last().unwrap().clone();
-		a * f
+		let mut r = self.factors.clone();
+		let mut q = vec![T::zero(); self.degree() - n + 1];
+		for i in (0..q.len()).rev() {
+			let f = r[i + n].clone() / rhs.factors[n].clone();
+			for (j, a) in rhs.factors.iter().enumerate() {
+				r[i + j] = r[i + j].clone() - f.clone() * a.clone();
+			}
+			q[i] = f;
+		}
+		Self::from(q).cleanup()
 	}
 }
 
@@ -269,7 +329,7 @@ impl<T> Polynomial<T> where T:
 		if self.degree() == 0 {
 			return vec!(self.clone())
 		}
-		let mut seq = vec![self.clone(), self.clone().derivative()]; // assuming square-free case
+		let mut seq = vec![self.clone(), self.clone().derivative()]; // ends with gcd(p, p') for multiple roots
 		while seq.last().unwrap().degree() > 0 {
 			let p = seq[seq.len()-2].clone() % seq.last().unwrap().clone();
 			seq.push(-p);
@@ -278,6 +338,126 @@ impl<T> Polynomial<T> where T:
 	}
 }
 
+impl<T> Polynomial<T> where T:
+	Zero
+	+ One
+	+ ops::Sub<Output=T>
+	+ ops::Mul<Output=T>
+	+ ops::Div<Output=T>
+	+ ops::Neg<Output=T>
+	+ From<i32>
+	+ Clone
+{
+// the remainder of lc(rhs)^(deg p - deg rhs + 1)·p by rhs, which needs no divisions
+	pub fn pseudo_remainder(&self, rhs: &Self) -> Self {
+		let n = rhs.degree();
+		if self.degree() < n {
+			return self.clone()
+		}
+		let lc = rhs.factors[n].clone();
+		let mut r = self.factors.clone();
+		for i in (0..=self.degree() - n).rev() {
+			let f = r[i + n].clone();
+			for x in r[..i + n].iter_mut() {
+				*x = x.clone() * lc.clone();
+			}
+			for (j, b) in rhs.factors[..n].iter().enumerate() {
+				r[i + j] = r[i + j].clone() - f.clone() * b.clone();
+			}
+			r.pop();
+		}
+		if r.is_empty() {
+			Self::zero()
+		} else {
+			Self::from(r)
+		}
+	}
+	
+// the resultant by the subresultant pseudo-remainder sequence (Cohen, algorithm 3.3.7), in which every division is exact,
+// so that exact coefficients stay small; zero iff p and rhs have a common root
+	pub fn resultant(&self, rhs: &Self) -> T {
+		if self.is_zero() || rhs.is_zero() {
+			return T::zero()
+		}
+		let pow = |x: &T, k: usize| (0..k).fold(T::one(), |a, _| a * x.clone());
+		let odd = |a: &Self, b: &Self| a.degree() % 2 == 1 && b.degree() % 2 == 1;
+		let (mut a, mut b) = (self.clone(), rhs.clone());
+		let mut s = T::one();
+		if a.degree() < b.degree() {
+			std::mem::swap(&mut a, &mut b);
+			if odd(&a, &b) {
+				s = -s;
+			}
+		}
+		let (mut g, mut h) = (T::one(), T::one());
+		while b.degree() > 0 {
+			let delta = a.degree() - b.degree();
+			if odd(&a, &b) {
+				s = -s;
+			}
+			let r = a.pseudo_remainder(&b);
+			let d = g.clone() * pow(&h, delta);
+			a = b;
+			b = Self::from(r.factors.into_iter().map(|x| x / d.clone()).collect::<Vec<_>>());
+			if b.is_zero() {
+				return T::zero()
+			}
+			g = a.factors.last().unwrap().clone();
+			if delta > 0 {
+				h = pow(&g, delta) / pow(&h, delta - 1);
+			}
+		}
+		let n = a.degree();
+		if n > 0 {
+			h = pow(&b.factors[0], n) / pow(&h, n - 1);
+		}
+		s * h
+	}
+	
+// (-1)^(n(n-1)/2)·Res(p, p')/a_n, a polynomial in the coefficients which vanishes iff p has a multiple root
+	pub fn discriminant(&self) -> T {
+		let n = self.degree();
+		let r = self.resultant(&self.clone().derivative()) / self.factors[n].clone();
+		if (n * n.saturating_sub(1) / 2) % 2 == 1 {
+			-r
+		} else {
+			r
+		}
+	}
+}
+
+// how the real roots are isolated: by Sturm sequences, or by Descartes' rule of signs on Taylor-shifted polynomials
+// over halved intervals (the Vincent–Collins–Akritas method), which needs no polynomial remainders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Isolation {
+	Sturm,
+	Descartes,
+}
+
+// the coefficients of p(x + h), by repeated synthetic division
+fn taylor_shift<T>(a: &mut [T], h: &T) where T: ops::Add<Output=T> + ops::Mul<Output=T> + Clone {
+	for i in 0..a.len() {
+		for j in (i..a.len()-1).rev() {
+			a[j] = a[j].clone() + h.clone() * a[j+1].clone();
+		}
+	}
+}
+
+// the sign changes of the coefficients of (x + 1)^n p(1/(x + 1)), a bound on the number of roots of p in (0, 1)
+// of the same parity, exact when it is 0 or 1
+fn descartes<T>(a: &[T]) -> usize where T: Signed + Clone {
+	let mut r = a.iter().rev().cloned().collect::<Vec<_>>();
+	taylor_shift(&mut r, &T::one());
+	let signs = r.iter().filter(|x| !x.is_zero()).map(|x| x.is_positive()).collect::<Vec<_>>();
+	signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+// the sign changes of a Sturm sequence at x, zeros skipped, so that they are exact with exact arithmetic
+fn sign_changes<T>(ss: &[Polynomial<T>], x: &T) -> usize where T: Signed + Clone {
+	let signs = ss.iter().map(|p| p.eval_ref(x)).filter(|y| !y.is_zero()).map(|y| y.is_positive()).collect::<Vec<_>>();
+	signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
 impl<T> Polynomial<T> where T:
 	Signed
 	+ ops::Sub<Output=T>
@@ -286,6 +466,14 @@ impl<T> Polynomial<T> where T:
 	+ PartialOrd
 	+ Clone
 {
+// number of distinct real roots in (left, right], from the sign changes of the Sturm sequence, where zeros are skipped;
+// it ends with the gcd of p and p' when roots are multiple, which does not change the count.
+// Exact whenever the arithmetic of T is; rounding may make it decrease to the right, which counts as none
+	pub fn count_roots(&self, left: &T, right: &T) -> usize {
+		let ss = self.sturm_sequence();
+		sign_changes(&ss, left).saturating_sub(sign_changes(&ss, right))
+	}
+
 	pub fn localize_roots(&self, left: T, right: T, eps: &T) -> Vec<(T, T)> {
 		if right <= left {
 			return vec![]
@@ -293,27 +481,87 @@ impl<T> Polynomial<T> where T:
 		self.try_localize_roots_internal(left, right, None, eps).unwrap()
 	}
 	
+	pub fn localize_roots_by(&self, isolation: Isolation, left: T, right: T, eps: &T) -> Vec<(T, T)> {
+		match isolation {
+			Isolation::Sturm => self.localize_roots(left, right, eps),
+			Isolation::Descartes => self.localize_roots_descartes(left, right, eps),
+		}
+	}
+
+// p(l + (r - l)x) is bisected on (0, 1), the halves being 2^n p(x/2) and its shift by 1, until the Descartes bound
+// is 0 or 1; as with localize_roots, the roots in (left, right] are returned, and those in an interval narrower
+// than eps that may still hold several are counted by the Sturm sequence
+	fn localize_roots_descartes(&self, left: T, right: T, eps: &T) -> Vec<(T, T)> {
+		if right <= left {
+			return vec![]
+		}
+		let two = T::from(2);
+		let mut a = self.factors.clone();
+		taylor_shift(&mut a, &left);
+		let mut s = T::one();
+		for c in a.iter_mut() {
+			*c = c.clone() * s.clone();
+			s = s * (right.clone() - left.clone());
+		}
+// a bracket must be one that refining expects, with p of opposite signs at its ends, or it is halved further:
+// not one ending at a root besides the one inside, nor one where rounding of the coefficients lost a root;
+// and a sign change means a root, whatever the rounded coefficients say
+		let opposite = |l: &T, r: &T| {
+			let (vl, vr) = (self.eval_ref(l), self.eval_ref(r));
+			!vl.is_zero() && !vr.is_zero() && vl.is_positive() != vr.is_positive()
+		};
+		let mut roots = Vec::new();
+		let mut stack = vec![(a, left, right.clone())];
+		while let Some((a, l, r)) = stack.pop() {
+			match descartes(&a) {
+				0 if !opposite(&l, &r) => continue,
+				1 if opposite(&l, &r) => {
+					roots.push((l, r));
+					continue
+				},
+				_ => {},
+			}
+			if r.clone() - l.clone() < *eps {
+				let n = self.count_roots(&l, &r).saturating_sub(self.eval_ref(&r).is_zero() as usize);
+				roots.append(&mut vec![(l, r); n]);
+				continue
+			}
+			let m = (l.clone() + r.clone()) / two.clone();
+			let mut b = a;
+			let mut s = T::one();
+			for c in b.iter_mut().rev() {
+				*c = c.clone() * s.clone();
+				s = s * two.clone();
+			}
+			let mut c = b.clone();
+			taylor_shift(&mut c, &T::one());
+			if self.eval_ref(&m).is_zero() {
+				roots.push((m.clone(), m.clone()));
+			}
+			stack.push((c, m.clone(), r));
+			stack.push((b, l, m));
+		}
+		if self.eval_ref(&right).is_zero() {
+			roots.push((right.clone(), right));
+		}
+		roots.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+		roots
+	}
+
 	pub fn try_localize_roots(&self, left: T, right: T, expected_roots: usize, eps: &T) -> Result<Vec<(T, T)>, usize> {
 		self.try_localize_roots_internal(left, right, Some(expected_roots), eps)
 	}
 	
 	fn try_localize_roots_internal(&self, left: T, right: T, expected_roots: Option<usize>, eps: &T) -> Result<Vec<(T, T)>, usize> {
 		let ss = self.sturm_sequence();
-		let (ssl, ssr) = (
-			ss.iter().map(|p| p.eval_ref(&left).is_positive()).collect::<Vec<_>>(),
-			ss.iter().map(|p| p.eval_ref(&right).is_positive()).collect::<Vec<_>>(),
-		);
-		let (csl, csr) = (
-			ssl.iter().zip(&ssl[1..]).filter(|(x, y)| *x^*y).count(),
-			ssr.iter().zip(&ssr[1..]).filter(|(x, y)| *x^*y).count(),
-		);
+		let (csl, csr) = (sign_changes(&ss, &left), sign_changes(&ss, &right));
 		if let Some(n) = expected_roots {
 			let r = csl - csr;
 			if r != n {
 				return Err(r)
 			}
 		}
-		let roots = self.localize_roots_internal(left, right, csl, csr, &ss, &eps);
+		let roots = self.localize_roots_internal(left, right, csl, csr, &ss, eps);
 		if let Some(n) = expected_roots {
 			let r = roots.len();
 			if r != n {
@@ -335,8 +583,7 @@ impl<T> Polynomial<T> where T:
 			return vec![(left, right); csl - csr]
 		}
 		let middle = (right.clone() + left.clone()) / (2i32).into();
-		let ssm = ss.iter().map(|p| p.eval_ref(&middle).is_positive()).collect::<Vec<_>>();
-		let csm = ssm.iter().zip(&ssm[1..]).filter(|(x, y)| *x^*y).count();
+		let csm = sign_changes(ss, &middle);
 		let (mut lrl, mut lrr) = (
 			self.localize_roots_internal(left, middle.clone(), csl, csm, ss, eps),
 			self.localize_roots_internal(middle, right, csm, csr, ss, eps)
@@ -345,23 +592,241 @@ impl<T> Polynomial<T> where T:
 		lrl
 	}
 	
-	pub fn find_roots(&self, left: T, right: T, eps: &T) -> Vec<T> {
-		self.localize_roots(left, right, eps).into_iter().map(|(mut l, mut r)| {
-			let vrp = self.eval_ref(&r).is_positive();
+// greatest common divisor, monic, by Euclid's algorithm: a remainder counts as zero when its coefficients are
+// below eps relative to the largest one of the dividend, so eps = 0 is exact with exact arithmetic
+	pub fn gcd(&self, rhs: &Self, eps: &T) -> Self {
+		let negligible = |r: &Self, a: &Self| {
+			let m = a.factors.iter().map(|x| x.abs()).fold(T::zero(), |m, x| if x > m {x} else {m});
+			r.factors.iter().all(|x| x.abs() <= eps.clone() * m.clone())
+		};
+		let (mut a, mut b) = (self.clone(), rhs.clone());
+		while !negligible(&b, &a) {
+			if b.degree() == 0 {
+				return Self::one()
+			}
+			let t = a % b.clone();
+			a = b;
+			b = t;
+		}
+		let f = T::one() / a.factors.last().unwrap().clone();
+		a * f
+	}
+	
+// greatest common divisor, monic, along the subresultant sequence: its pseudo-remainders, divided exactly by g·h^δ,
+// keep exact coefficients from growing, where those of Euclid's algorithm blow up. A pseudo-remainder counts as zero
+// when its coefficients are below eps relative to the largest one of lc(b)^(δ+1)·a
+	pub fn subresultant_gcd(&self, rhs: &Self, eps: &T) -> Self {
+		let pow = |x: &T, k: usize| (0..k).fold(T::one(), |a, _| a * x.clone());
+		let max = |a: &Self| a.factors.iter().map(|x| x.abs()).fold(T::zero(), |m, x| if x > m {x} else {m});
+		let monic = |a: Self| {
+			let f = T::one() / a.factors.last().unwrap().clone();
+			a * f
+		};
+		let (mut a, mut b) = if self.degree() >= rhs.degree() {(self.clone(), rhs.clone())} else {(rhs.clone(), self.clone())};
+		if max(&b) <= eps.clone() * max(&a) {
+			return monic(a)
+		}
+		let (mut g, mut h) = (T::one(), T::one());
+		loop {
+			if b.degree() == 0 {
+				return Self::one()
+			}
+			let delta = a.degree() - b.degree();
+			let r = a.pseudo_remainder(&b);
+			if max(&r) <= eps.clone() * max(&a) * pow(&b.factors.last().unwrap().abs(), delta + 1) {
+				return monic(b)
+			}
+			let d = g.clone() * pow(&h, delta);
+			a = b;
+			b = Self::from(r.factors.into_iter().map(|x| x / d.clone()).collect::<Vec<_>>());
+			g = a.factors.last().unwrap().clone();
+			if delta > 0 {
+				h = pow(&g, delta) / pow(&h, delta - 1);
+			}
+		}
+	}
+
+// the square-free factors f_m of p = c·f_1·f_2^2·f_3^3…, by Yun's algorithm, without those of degree 0; the gcds
+// drop remainders below the relative tolerance tol. None when rounding makes the factors inconsistent with the degree of p.
+// With exact arithmetic, tol = 0, the gcds follow the subresultant sequence, whose coefficients stay small; with rounding,
+// Euclid's algorithm, since the subresultants grow like determinants of the coefficients and overflow f64
+	pub fn square_free(&self, tol: &T) -> Option<Vec<(Self, usize)>> {
+		let gcd = |a: &Self, b: &Self| if tol.is_zero() {a.subresultant_gcd(b, tol)} else {a.gcd(b, tol)};
+		let d = self.clone().derivative();
+		let a = gcd(self, &d);
+		let (mut b, mut c) = (self.quotient(&a), d.quotient(&a));
+		let mut factors = Vec::new();
+		let mut m = 1;
+		while b.degree() > 0 && m <= self.degree() {
+			let d = c - b.clone().derivative();
+			let f = gcd(&b, &d);
+			b = b.quotient(&f);
+			c = d.quotient(&f);
+			if f.degree() > 0 {
+				factors.push((f, m));
+			}
+			m += 1;
+		}
+		if b.degree() > 0 || factors.iter().map(|(f, m)| f.degree() * m).sum::<usize>() != self.degree() {
+			return None
+		}
+		Some(factors)
+	}
+
+// the roots to eps, each with its multiplicity, from the square-free factors; None when the factorisation failed
+	pub fn find_roots_with_multiplicity(&self, left: T, right: T, eps: &T, tol: &T) -> Option<Vec<(T, usize)>> {
+		self.find_roots_with_multiplicity_by(Isolation::Sturm, left, right, eps, tol)
+	}
+
+	pub fn find_roots_with_multiplicity_by(&self, isolation: Isolation, left: T, right: T, eps: &T, tol: &T) -> Option<Vec<(T, usize)>> {
+		let mut roots = self.square_free(tol)?.into_iter().flat_map(|(f, m)| {
+			f.find_simple_roots(isolation, left.clone(), right.clone(), eps).into_iter().map(move |x| (x, m))
+		}).collect::<Vec<_>>();
+		roots.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+		Some(roots)
+	}
+
+// the roots, repeated according to their multiplicity; when the factorisation fails, only those that p itself brackets,
+// once each, so that the count falls short and the callers, which know how many roots to expect, retry with more accuracy
+	pub fn find_roots(&self, left: T, right: T, eps: &T, tol: &T) -> Vec<T> {
+		self.find_roots_by(Isolation::Sturm, left, right, eps, tol)
+	}
+
+	pub fn find_roots_by(&self, isolation: Isolation, left: T, right: T, eps: &T, tol: &T) -> Vec<T> {
+		match self.find_roots_with_multiplicity_by(isolation, left.clone(), right.clone(), eps, tol) {
+			Some(roots) => roots.into_iter().flat_map(|(x, m)| vec![x; m]).collect(),
+			None => self.find_simple_roots(isolation, left, right, eps),
+		}
+	}
+
+// every root is refined inside its isolating interval by Newton steps with the derivative, safeguarded by bisection
+// when a step would leave the interval or the last one did not halve it; after a short step, the point as far
+// on the other side is probed too, which brackets the root tightly once the convergence is quadratic
+	fn find_simple_roots(&self, isolation: Isolation, left: T, right: T, eps: &T) -> Vec<T> {
+		let d = self.clone().derivative();
+		let two = T::from(2);
+		self.localize_roots_by(isolation, left, right, eps).into_iter().map(|(mut l, mut r)| {
+// a bracket (l, r] may end at its root
+			let vr = self.eval_ref(&r);
+			if vr.is_zero() {
+				return r
+			}
+			let vrp = vr.is_positive();
+			let mut x = (l.clone() + r.clone()) / two.clone();
+			let mut probe: Option<T> = None;
+			let mut last = r.clone() - l.clone();
 			while r.clone() - l.clone() > *eps {
-				let m = (r.clone() + l.clone()) / (2i32).into();
-				let vmp = self.eval_ref(&m).is_positive();
-				if vmp == vrp {
-					r = m;
+				let fx = self.eval_ref(&x);
+				if fx.is_zero() {
+					return x
+				}
+				let right_of_root = fx.is_positive() == vrp;
+				if right_of_root {
+					r = x.clone();
 				} else {
-					l = m;
+					l = x.clone();
+				}
+				if let Some(h) = probe.take() {
+					let y = if right_of_root {x.clone() - h} else {x.clone() + h};
+					if l < y && y < r {
+						let fy = self.eval_ref(&y);
+						if fy.is_zero() {
+							return y
+						}
+						if fy.is_positive() == vrp {
+							r = y;
+						} else {
+							l = y;
+						}
+					}
 				}
+				let width = r.clone() - l.clone();
+				let dx = d.eval_ref(&x);
+				let step = if dx.is_zero() {None} else {Some(fx / dx)};
+				match step.map(|s| (x.clone() - s.clone(), s.abs())) {
+					Some((n, h)) if l < n && n < r && h.clone() * two.clone() < width && width.clone() * two.clone() <= last => {
+						if h.clone() * T::from(4) < width {
+							probe = Some(h);
+						}
+						x = n;
+					},
+					_ => x = (l.clone() + r.clone()) / two.clone(),
+				}
+				last = width;
 			}
 			r
 		}).collect()
 	}
 }
 
+// a number whose sign may be unknown, like an interval enclosing zero
+pub trait Certified: Sized {
+	fn certain_sign(&self) -> Option<i8>;
+// an exactly known point the fraction k/n of the way from a to b
+	fn between(a: &Self, b: &Self, k: i32, n: i32) -> Self;
+}
+
+impl<T> Polynomial<T> where T:
+	Zero
+	+ One
+	+ ops::Sub<Output=T>
+	+ ops::Mul<Output=T>
+	+ ops::Div<Output=T>
+	+ ops::Neg<Output=T>
+	+ From<i32>
+	+ PartialOrd
+	+ Clone
+	+ Certified
+{
+// roots in (left, right] localized by Sturm counts only when all the signs are certain: every returned pair encloses a root
+// (a cluster narrower than eps is repeated), or the error is the part of (left, right] where no certain count was found;
+// near a root the midpoint is shifted if the signs there cannot be decided
+	pub fn certified_roots(&self, left: T, right: T, eps: &T) -> Result<Vec<(T, T)>, (T, T)> {
+		let ss = self.sturm_sequence();
+		let count = |x: &T| {
+			let mut signs = Vec::new();
+			for p in &ss {
+				match p.eval_ref(x).certain_sign()? {
+					0 => {},
+					s => signs.push(s),
+				}
+			}
+			Some(signs.windows(2).filter(|w| w[0] != w[1]).count())
+		};
+		let (csl, csr) = match (count(&left), count(&right)) {
+			(Some(csl), Some(csr)) if csl >= csr => (csl, csr),
+			_ => return Err((left, right)),
+		};
+		let mut roots = Vec::new();
+		let mut stack = vec![(left, right, csl, csr)];
+		while let Some((l, r, csl, csr)) = stack.pop() {
+			if csl == csr {
+				continue
+			}
+			if r.clone() - l.clone() < *eps {
+				roots.append(&mut vec![(l, r); csl - csr]);
+				continue
+			}
+			let split = [(1, 2), (1, 3), (2, 3), (1, 4), (3, 4)].iter().find_map(|&(k, n)| {
+				let m = T::between(&l, &r, k, n);
+				if m <= l || m >= r {
+					return None
+				}
+				count(&m).filter(|csm| *csm <= csl && *csm >= csr).map(|csm| (m, csm))
+			});
+			match split {
+				Some((m, csm)) => {
+					stack.push((m.clone(), r, csm, csr));
+					stack.push((l, m, csl, csm));
+				},
+				None if csl - csr == 1 => roots.push((l, r)),
+				None => return Err((l, r)),
+			}
+		}
+		Ok(roots)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -371,7 +836,7 @@ mod tests {
 		m: Vec<Vec<f32>>,
 	}
 	
-	impl<'a> ops::Mul<&'a f32> for Matrix {
+	impl ops::Mul<&f32> for Matrix {
 		type Output = Matrix;
 		fn mul(mut self, rhs: &f32) -> Matrix {
 			for row in &mut self.m {
@@ -508,6 +973,15 @@ mod tests {
 		assert_eq!(p2 * p1, p);
 	}
 	
+	#[test]
+	fn karatsuba() {
+		let p1 = Polynomial::from((0..45).map(|i| (i * 7 % 11) - 5).collect::<Vec<i64>>());
+		let p2 = Polynomial::from((0..38).map(|i| (i * 5 % 13) - 6).collect::<Vec<i64>>());
+		assert_eq!(p1.karatsuba(&p2), p1.clone() * p2.clone());
+		assert_eq!(p2.karatsuba(&p1), p1.clone() * p2.clone());
+		assert_eq!(p1.karatsuba(&Polynomial::from(vec![3, 1])), p1 * Polynomial::from(vec![3, 1]));
+	}
+	
 	#[test]
 	fn display() {
 		assert_eq!(
@@ -553,11 +1027,30 @@ mod tests {
 	fn gcd() {
 		assert_eq!(
 			Polynomial::from(vec![-9.0, 0.0, 1.0])
-			._gcd(&Polynomial::from(vec![-6.0, 1.0, 1.0])),
+			.gcd(&Polynomial::from(vec![-6.0, 1.0, 1.0]), &0.0),
 			Polynomial::from(vec![3.0, 1.0])
 		);
 	}
 	
+	#[test]
+	fn resultant() {
+		let a = Polynomial::from(vec![1.0, 3.0, 2.0]);
+		let b = Polynomial::from(vec![-6.0, -1.0, 1.0]);
+		// 2^2·b(-1)·b(-1/2)
+		assert_eq!(a.resultant(&b), 84.0);
+		assert_eq!(b.resultant(&a), 84.0);
+		assert_eq!(a.resultant(&Polynomial::from(vec![2.0, 1.0])), 3.0);
+		assert_eq!(a.resultant(&(a.clone() * Polynomial::from(vec![5.0, 1.0]))), 0.0);
+		assert_eq!(Polynomial::from(vec![2.0, -3.0, 1.0]).discriminant(), 1.0);
+		// x^3 + px + q: -4p^3 - 27q^2
+		assert_eq!(Polynomial::from(vec![-4.0, -6.0, 0.0, 1.0]).discriminant(), 864.0 - 432.0);
+		assert_eq!(Polynomial::from(vec![2.0, -3.0, 0.0, 1.0]).discriminant(), 0.0);
+		let p = Polynomial::from(vec![-9.0, 0.0, 1.0]) * Polynomial::from(vec![1.0, 1.0, 1.0]);
+		let q = Polynomial::from(vec![-6.0, 1.0, 1.0]) * Polynomial::from(vec![2.0, 0.0, 1.0]);
+		assert_eq!(p.subresultant_gcd(&q, &0.0), Polynomial::from(vec![3.0, 1.0]));
+		assert_eq!(q.subresultant_gcd(&(q.clone() * Polynomial::from(vec![1.0, 1.0])), &0.0), q);
+	}
+	
 	#[test]
 	fn sturm() {
 		assert_eq!(
@@ -576,8 +1069,65 @@ mod tests {
 	fn roots() {
 		let eps = 1e-14_f64;
 		let r = Polynomial::from(vec![-4.0, 0.0, 1.0])
-			.find_roots(-5.0, 5.0, &eps);
+			.find_roots(-5.0, 5.0, &eps, &eps);
 		let r2 = vec![-2.0, 2.0];
 		assert!(r.len() == r2.len() && r.into_iter().zip(r2.into_iter()).all(|(x, x2)| (x-x2).abs() <= eps));
 	}
+
+	#[test]
+	fn multiple_roots() {
+		// (x - 1)^2 (x + 2)^3 x
+		let p = Polynomial::from(vec![1.0, -1.0]) * Polynomial::from(vec![1.0, -1.0])
+			* Polynomial::from(vec![2.0, 1.0]) * Polynomial::from(vec![2.0, 1.0]) * Polynomial::from(vec![2.0, 1.0])
+			* Polynomial::from(vec![0.0, 1.0]);
+		let f = p.square_free(&1e-12).unwrap();
+		assert_eq!(f.iter().map(|(f, m)| (f.degree(), *m)).collect::<Vec<_>>(), vec![(1, 1), (1, 2), (1, 3)]);
+		let r = p.find_roots_with_multiplicity(-5.0, 5.0, &1e-12, &1e-12).unwrap();
+		assert_eq!(r.iter().map(|x| x.1).collect::<Vec<_>>(), vec![3, 1, 2]);
+		assert!(r.iter().zip(&[-2.0, 0.0, 1.0]).all(|(x, y)| (x.0 - y).abs() < 1e-10));
+		assert_eq!(p.find_roots(-5.0, 5.0, &1e-12, &1e-12).len(), 6);
+		assert_eq!(p.quotient(&Polynomial::from(vec![0.0, 1.0])).degree(), 5);
+		// a tolerance that takes p' for noise: no factorisation, and fewer roots
+		assert!(p.square_free(&1.0).is_none() && p.find_roots_with_multiplicity(-5.0, 5.0, &1e-12, &1.0).is_none());
+		assert!(p.find_roots(-5.0, 5.0, &1e-12, &1.0).len() < 6);
+	}
+
+	#[test]
+	fn refinement() {
+		// √2 to 60 digits, and the roots of x^3 - 2x + 1 = (x - 1)(x^2 + x - 1)
+		let eps = bigdecimal::BigDecimal::new(1.into(), 60);
+		let r = Polynomial::from(vec![(-2).into(), 0.into(), bigdecimal::BigDecimal::from(1)]).find_roots(0.into(), 5.into(), &eps, &eps);
+		let sqrt2: bigdecimal::BigDecimal = "1.414213562373095048801688724209698078569671875376948073176679737990732".parse().unwrap();
+		assert!(r.len() == 1 && (r[0].clone() - sqrt2).abs() <= eps);
+		let r = Polynomial::from(vec![1.0, -2.0, 0.0, 1.0]).find_roots(-2.0, 2.0, &1e-14, &1e-14);
+		let r2 = [-(1.0 + 5f64.sqrt()) / 2.0, (5f64.sqrt() - 1.0) / 2.0, 1.0];
+		assert!(r.len() == 3 && r.iter().zip(&r2).all(|(x, y)| (x - y).abs() <= 2e-14));
+	}
+
+	#[test]
+	fn descartes() {
+		// roots at the midpoints of the bisection and at the right end: x(x - 1)(x + 1)(x - 2)
+		let p = Polynomial::from(vec![0.0, -1.0, 0.0, 1.0]) * Polynomial::from(vec![-2.0, 1.0]);
+		let r = p.localize_roots_by(Isolation::Descartes, -2.0, 2.0, &1e-14);
+		assert_eq!(r.len(), 4);
+		assert!(r.iter().zip(&[-1.0, 0.0, 1.0, 2.0]).all(|((l, r), x)| l <= x && x <= r));
+		assert_eq!(p.localize_roots_by(Isolation::Descartes, -1.0, 2.0, &1e-14).len(), 3);
+		// (x - 2)(x^2 + 2x - 2): the root 2 at the midpoint ends the bracket of √3 - 1
+		let p = Polynomial::from(vec![4.0, -6.0, 0.0, 1.0]);
+		let r = p.find_roots_by(Isolation::Descartes, -4.0, 4.0, &1e-14, &1e-14);
+		assert!(r.len() == 3 && r.iter().zip(&[-1.0 - 3f64.sqrt(), 3f64.sqrt() - 1.0, 2.0]).all(|(x, y)| (x - y).abs() <= 1e-14));
+		// ±10^-10, closer than eps but on either side of a midpoint, and 1 ± 10^-10, counted by the Sturm sequence
+		let b = |x: &str| x.parse::<bigdecimal::BigDecimal>().unwrap();
+		let p = Polynomial::from(vec![b("-1e-20"), b("0"), b("1")]) * Polynomial::from(vec![b("-4"), b("0"), b("1")]);
+		let r = p.localize_roots_by(Isolation::Descartes, b("-5"), b("5"), &b("1e-6"));
+		assert!(r.len() == 4 && r[1].1 == b("0") && r[2].0 == b("0"));
+		let p = Polynomial::from(vec![b("0.99999999999999999999"), b("-2"), b("1")]) * Polynomial::from(vec![b("-4"), b("0"), b("1")]);
+		let r = p.localize_roots_by(Isolation::Descartes, b("-5"), b("5"), &b("1e-6"));
+		assert!(r.len() == 4 && r[1] == r[2] && r[1].1.clone() - r[1].0.clone() < b("1e-6"));
+		let eps = 1e-14;
+		let q = Polynomial::from(vec![-1.0, -1.0, 0.0, 1.0, 1.0]) * Polynomial::from(vec![0.5, -3.0, 1.0]);
+		let s = q.find_roots_by(Isolation::Sturm, -5.0, 5.0, &eps, &eps);
+		let d = q.find_roots_by(Isolation::Descartes, -5.0, 5.0, &eps, &eps);
+		assert!(s.len() == 4 && s.len() == d.len() && s.iter().zip(&d).all(|(x, y)| (x - y).abs() <= eps));
+	}
 }
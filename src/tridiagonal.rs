@@ -0,0 +1,117 @@
+use crate::Appr;
+
+// real symmetric q×q matrix with the given diagonal, unit hoppings between neighbours
+// and the hopping `corner` between the last and the first site (±1 for periodic or antiperiodic boundary conditions)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cyclic<T> {
+	diag: Vec<T>,
+	corner: T,
+}
+
+impl<T: Appr> Cyclic<T> {
+	pub fn new(diag: Vec<T>, corner: T) -> Self {
+		assert!(!diag.is_empty());
+		Cyclic {
+			diag,
+			corner,
+		}
+	}
+
+// number of eigenvalues below x: the number of negative eigenvalues of the pivots of the block LDL^T decomposition
+// of H - x (Sylvester's law of inertia); eliminating the sites in order only fills in the column of the last site,
+// and small pivots are avoided by eliminating two sites at once (as in the Bunch–Kaufman pivoting)
+	pub fn count_below(&self, x: &T, accu: i64) -> usize {
+		let n = self.diag.len();
+		let a = |m: usize| self.diag[m].clone() - x.clone();
+// inertia of the symmetric 2×2 block [[d, b], [b, e]]
+		let negatives = |d: &T, b: &T, e: &T| {
+			let det = d.clone() * e.clone() - b.clone() * b.clone();
+			if det.is_negative() {1} else if (d.clone() + e.clone()).is_negative() {2 - det.is_zero() as usize} else {0}
+		};
+		match n {
+			1 => return (a(0) + T::from(2) * self.corner.clone()).is_negative() as usize,
+			2 => return negatives(&a(0), &(T::one() + self.corner.clone()), &a(1)),
+			_ => {},
+		}
+// the hopping of site m to the last site before the elimination
+		let last = |m: usize| if m == n - 2 {T::one()} else {T::zero()};
+		let alpha = T::one() / T::from(2);
+		let mut neg = 0;
+		let (mut d, mut f, mut s) = (a(0), self.corner.clone(), a(n-1));
+		let mut m = 0;
+		while m < n - 2 {
+			let big = if f.abs() > T::one() {f.abs()} else {T::one()};
+// a 2×2 pivot is only taken when it is far from singular: |det| >= 1/2
+			if d.abs() >= alpha.clone() * big || (d.clone() * a(m+1)).abs() > alpha {
+				neg += d.is_negative() as usize;
+				let g = (f / d.clone()).accu(accu);
+				s = s - (g.clone() * g.clone() * d.clone()).accu(accu);
+				f = last(m+1) - g;
+				d = a(m+1) - (T::one() / d).accu(accu);
+				m += 1;
+			} else {
+				let (e, h) = (a(m+1), last(m+1));
+				neg += negatives(&d, &T::one(), &e);
+				let det = d.clone() * e.clone() - T::one();
+				s = s - ((e * f.clone() * f.clone() - T::from(2) * f.clone() * h.clone() + d.clone() * h.clone() * h.clone()) / det.clone()).accu(accu);
+				if m + 2 == n - 1 {
+					return neg + s.is_negative() as usize
+				}
+				f = last(m+2) + ((f - d.clone() * h) / det.clone()).accu(accu);
+				d = a(m+2) - (d / det).accu(accu);
+				m += 2;
+			}
+		}
+		neg + negatives(&d, &f, &s)
+	}
+
+// all eigenvalues, inside [-bound, bound], by bisection on the eigenvalue counts to one more digit than required
+	pub fn eigenvalues(&self, bound: &T, accu: i64) -> Vec<T> {
+		let eps = T::eps(accu + 1);
+		let two = T::from(2);
+// `accu` truncates, so the result is corrected to the nearest representable value
+		let half_ulp = T::eps(accu) / two.clone();
+		let round = |x: T| {
+			if x.abs() < half_ulp {
+				return T::zero()
+			}
+			let r = x.clone().accu(accu);
+			if (x.clone() - r.clone()).abs() <= half_ulp {r} else {(r + T::eps(accu) * x.signum()).accu(accu)}
+		};
+		(0..self.diag.len()).map(|k| {
+			let (mut lo, mut hi) = (-bound.clone(), bound.clone());
+			while hi.clone() - lo.clone() > eps {
+				let mid = ((lo.clone() + hi.clone()) / two.clone()).accu(accu + 2);
+				if mid <= lo || mid >= hi {
+					break
+				}
+				if self.count_below(&mid, accu + 2) > k {
+					hi = mid;
+				} else {
+					lo = mid;
+				}
+			}
+			round((lo + hi) / two.clone())
+		}).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn count() {
+		// periodic ring of 4 sites: 2cos(2πk/4) = -2, 0, 0, 2
+		let h = Cyclic::new(vec![0.0; 4], 1.0);
+		assert_eq!([-3.0, -1.0, 1.0, 3.0].iter().map(|x| h.count_below(x, 0)).collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+		let e = h.eigenvalues(&3.0, 0);
+		assert!(e.iter().zip(&[-2.0, 0.0, 0.0, 2.0]).all(|(a, b)| (a - b).abs() < 1e-12));
+		// antiperiodic ring of 3 sites: 2cos(π(2k+1)/3) = 1, -2, 1, shifted by the diagonal
+		let e = Cyclic::new(vec![0.5; 3], -1.0).eigenvalues(&4.0, 0);
+		assert!(e.iter().zip(&[-1.5, 1.5, 1.5]).all(|(a, b)| (a - b).abs() < 1e-12));
+		let e = Cyclic::new(vec![1.0, -1.0], 1.0).eigenvalues(&4.0, 0);
+		assert!(e.iter().zip(&[-5f64.sqrt(), 5f64.sqrt()]).all(|(a, b)| (a - b).abs() < 1e-12));
+		assert!((Cyclic::new(vec![0.5], -1.0).eigenvalues(&4.0, 0)[0] + 1.5).abs() < 1e-12);
+	}
+}